@@ -0,0 +1,164 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitChar, spanned::Spanned};
+
+// Generates a `Draw` implementation that renders the struct as a bordered box,
+// one "name: value" line per field. Supported attributes:
+//
+// - `#[draw(border = '#')]` on the struct swaps the default `+ - |` border for
+//   a single character.
+// - `#[draw(label)]` on a field uses its value as the title of the box.
+// - `#[draw(skip)]` on a field leaves it out of the box.
+// - `#[draw(debug)]` on a field formats it with `{:?}` instead of `{}`.
+#[proc_macro_derive(Draw, attributes(draw))]
+pub fn draw_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse_macro_input!(input as DeriveInput);
+
+    // Any misuse is reported as a compile error pointing at the offending
+    // tokens rather than panicking inside the compiler.
+    impl_draw(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct FieldOptions {
+    label: bool,
+    skip: bool,
+    debug: bool,
+}
+
+fn impl_draw(ast: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let fields = match &ast.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    data.fields.span(),
+                    "`#[derive(Draw)]` requires a struct with named fields",
+                ));
+            }
+        },
+        Data::Enum(data) => {
+            return Err(syn::Error::new(
+                data.enum_token.span,
+                "`#[derive(Draw)]` can only be applied to structs",
+            ));
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "`#[derive(Draw)]` can only be applied to structs",
+            ));
+        }
+    };
+
+    let mut border = None;
+    for attr in ast.attrs.iter().filter(|attr| attr.path().is_ident("draw")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("border") {
+                let lit: LitChar = meta.value()?.parse()?;
+                border = Some(lit.value());
+                Ok(())
+            } else {
+                Err(meta.error("unsupported struct attribute, expected `border = '<char>'`"))
+            }
+        })?;
+    }
+
+    let mut label = None;
+    let mut rows = Vec::new();
+    for field in fields {
+        let options = field_options(field)?;
+        let ident = field
+            .ident
+            .as_ref()
+            .expect("named fields always have an ident");
+
+        if options.label {
+            if label.is_some() {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "only one field can be marked `#[draw(label)]`",
+                ));
+            }
+            label = Some(quote! {
+                .with_label(::std::string::ToString::to_string(&value.#ident))
+            });
+            continue;
+        }
+        if options.skip {
+            continue;
+        }
+
+        let key = ident.to_string();
+        let rendered = if options.debug {
+            quote! { ::std::format!("{:?}", value.#ident) }
+        } else {
+            quote! { ::std::string::ToString::to_string(&value.#ident) }
+        };
+        rows.push(quote! { .with_field(#key, #rendered) });
+    }
+
+    let border = border.map(|c| quote! { .with_border(#c) });
+
+    let generated = quote! {
+        impl #impl_generics ::core::convert::From<&#name #ty_generics> for ::oop::BorderedBox
+        #where_clause
+        {
+            fn from(value: &#name #ty_generics) -> Self {
+                ::oop::BorderedBox::new()
+                    #border
+                    #label
+                    #(#rows)*
+            }
+        }
+
+        impl #impl_generics ::oop::Draw for #name #ty_generics #where_clause {
+            fn draw(&self) {
+                ::std::println!("{}", ::oop::BorderedBox::from(self));
+            }
+        }
+    };
+    Ok(generated)
+}
+
+fn field_options(field: &syn::Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions {
+        label: false,
+        skip: false,
+        debug: false,
+    };
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("draw"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("label") {
+                options.label = true;
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+            } else if meta.path.is_ident("debug") {
+                options.debug = true;
+            } else {
+                return Err(
+                    meta.error("unsupported field attribute, expected `label`, `skip` or `debug`")
+                );
+            }
+            Ok(())
+        })?;
+    }
+
+    if options.label && options.skip {
+        return Err(syn::Error::new(
+            field.span(),
+            "a field cannot be both `#[draw(label)]` and `#[draw(skip)]`",
+        ));
+    }
+    Ok(options)
+}
@@ -0,0 +1,87 @@
+use std::fmt;
+
+// The default renderer that `#[derive(Draw)]` builds for a struct: an optional
+// label in the top edge and one "name: value" line per field, e.g.
+//
+// +- OK -------+
+// | width: 50  |
+// | height: 10 |
+// +------------+
+pub struct BorderedBox {
+    label: Option<String>,
+    fields: Vec<(String, String)>,
+    border: Option<char>,
+}
+
+impl BorderedBox {
+    pub fn new() -> BorderedBox {
+        BorderedBox {
+            label: None,
+            fields: Vec::new(),
+            border: None,
+        }
+    }
+
+    // Draws every edge and corner with `c` instead of `+`, `-` and `|`.
+    pub fn with_border(mut self, c: char) -> BorderedBox {
+        self.border = Some(c);
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> BorderedBox {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_field(mut self, name: impl Into<String>, value: impl Into<String>) -> BorderedBox {
+        self.fields.push((name.into(), value.into()));
+        self
+    }
+}
+
+impl Default for BorderedBox {
+    fn default() -> Self {
+        BorderedBox::new()
+    }
+}
+
+impl fmt::Display for BorderedBox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (corner, horizontal, vertical) = match self.border {
+            Some(c) => (c, c, c),
+            None => ('+', '-', '|'),
+        };
+
+        let lines: Vec<String> = self
+            .fields
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect();
+
+        // Leave room for at least one horizontal on either side of the label.
+        let label_width = self
+            .label
+            .as_ref()
+            .map_or(0, |label| label.chars().count() + 2);
+        let inner = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(label_width);
+        let edge = |len: usize| horizontal.to_string().repeat(len);
+
+        match &self.label {
+            Some(label) => {
+                // "+- label ---+": pad with horizontals after "- label ".
+                let rest = inner + 2 - (label_width + 1);
+                writeln!(f, "{corner}{horizontal} {label} {}{corner}", edge(rest))?;
+            }
+            None => writeln!(f, "{corner}{}{corner}", edge(inner + 2))?,
+        }
+        for line in &lines {
+            writeln!(f, "{vertical} {line:<inner$} {vertical}")?;
+        }
+        write!(f, "{corner}{}{corner}", edge(inner + 2))
+    }
+}
@@ -1,3 +1,10 @@
+mod bordered_box;
+
+pub use bordered_box::BorderedBox;
+// `#[derive(Draw)]` lives in the `draw_derive` proc-macro crate. Macros and
+// traits live in different namespaces, so both can be called `Draw`.
+pub use draw_derive::Draw;
+
 // Instead of explict inheritance, we can mimic same concept through
 // traits to abstract common behaviour.
 pub trait Draw {
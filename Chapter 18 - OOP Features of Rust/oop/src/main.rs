@@ -1,4 +1,14 @@
-use oop::{Button, Screen, SelectBox};
+use oop::{Button, Draw, Screen, SelectBox};
+
+// Rather than writing `draw` by hand like `Button` and `SelectBox` do, we can
+// let `#[derive(Draw)]` render the fields for us.
+#[derive(Draw)]
+struct TextField {
+    #[draw(label)]
+    name: String,
+    width: u32,
+    placeholder: String,
+}
 
 fn main() {
     let screen = Screen {
@@ -17,6 +27,11 @@ fn main() {
                 height: 10,
                 label: String::from("OK"),
             }),
+            Box::new(TextField {
+                name: String::from("Username"),
+                width: 30,
+                placeholder: String::from("jane.doe"),
+            }),
         ],
     };
 
@@ -0,0 +1,110 @@
+use oop::{BorderedBox, Draw};
+
+#[derive(Draw)]
+struct Button {
+    #[draw(label)]
+    label: String,
+    width: u32,
+    height: u32,
+}
+
+#[derive(Draw)]
+#[draw(border = '#')]
+struct SelectBox {
+    width: u32,
+    #[draw(debug)]
+    options: Vec<&'static str>,
+    #[draw(skip)]
+    #[allow(dead_code)]
+    selected: usize,
+}
+
+#[derive(Draw)]
+struct Badge<T: std::fmt::Display> {
+    value: T,
+}
+
+#[test]
+fn renders_label_in_top_edge() {
+    let button = Button {
+        label: String::from("OK"),
+        width: 50,
+        height: 10,
+    };
+
+    assert_eq!(
+        BorderedBox::from(&button).to_string(),
+        "+- OK -------+\n\
+         | width: 50  |\n\
+         | height: 10 |\n\
+         +------------+"
+    );
+}
+
+#[test]
+fn custom_border_debug_and_skipped_fields() {
+    let select = SelectBox {
+        width: 75,
+        options: vec!["Yes", "No"],
+        selected: 0,
+    };
+
+    assert_eq!(
+        BorderedBox::from(&select).to_string(),
+        "##########################\n\
+         # width: 75              #\n\
+         # options: [\"Yes\", \"No\"] #\n\
+         ##########################"
+    );
+}
+
+#[test]
+fn generic_structs_are_supported() {
+    let badge = Badge { value: 3.5 };
+
+    assert_eq!(
+        BorderedBox::from(&badge).to_string(),
+        "+------------+\n\
+         | value: 3.5 |\n\
+         +------------+"
+    );
+}
+
+#[test]
+fn label_wider_than_fields_stretches_the_box() {
+    let button = Button {
+        label: String::from("Submit order"),
+        width: 5,
+        height: 1,
+    };
+
+    assert_eq!(
+        BorderedBox::from(&button).to_string(),
+        "+- Submit order -+\n\
+         | width: 5       |\n\
+         | height: 1      |\n\
+         +----------------+"
+    );
+}
+
+#[test]
+fn derived_types_fit_on_a_screen() {
+    let screen = oop::Screen {
+        components: vec![
+            Box::new(Button {
+                label: String::from("OK"),
+                width: 50,
+                height: 10,
+            }),
+            Box::new(Badge { value: "new" }),
+        ],
+    };
+
+    screen.run();
+}
+
+#[test]
+fn misuse_fails_to_compile() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
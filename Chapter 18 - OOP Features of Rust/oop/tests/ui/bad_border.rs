@@ -0,0 +1,9 @@
+use oop::Draw;
+
+#[derive(Draw)]
+#[draw(border = "##")]
+struct Button {
+    label: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use oop::Draw;
+
+#[derive(Draw)]
+struct Button {
+    #[draw(label)]
+    label: String,
+    #[draw(label)]
+    tooltip: String,
+}
+
+fn main() {}
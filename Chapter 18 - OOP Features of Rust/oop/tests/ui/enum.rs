@@ -0,0 +1,9 @@
+use oop::Draw;
+
+#[derive(Draw)]
+enum Shape {
+    Circle,
+    Square,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use oop::Draw;
+
+#[derive(Draw)]
+struct SelectBox {
+    options: Vec<String>,
+}
+
+fn main() {}
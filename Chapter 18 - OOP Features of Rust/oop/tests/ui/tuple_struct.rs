@@ -0,0 +1,6 @@
+use oop::Draw;
+
+#[derive(Draw)]
+struct Point(i32, i32);
+
+fn main() {}
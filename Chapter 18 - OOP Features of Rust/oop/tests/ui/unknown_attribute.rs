@@ -0,0 +1,9 @@
+use oop::Draw;
+
+#[derive(Draw)]
+struct Button {
+    #[draw(colour)]
+    label: String,
+}
+
+fn main() {}
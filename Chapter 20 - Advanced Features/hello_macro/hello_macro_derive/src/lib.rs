@@ -1,24 +1,166 @@
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
+use syn::LitStr;
 
-#[proc_macro_derive(HelloMacro)]
+mod route;
+mod sql;
+
+// Used when the type doesn't provide its own `#[hello(greeting = "...")]`.
+const DEFAULT_GREETING: &str = "Hello, Macro! My name is {name}!";
+
+#[proc_macro_derive(HelloMacro, attributes(hello))]
 pub fn hello_macro_derive(input: TokenStream) -> TokenStream {
     // Construct a representation of Rust code as a syntax tree
-    // that we can manipulate.
-    let ast = syn::parse(input).unwrap();
+    // that we can manipulate. Bad input becomes a compile error
+    // instead of a panic inside the compiler.
+    let ast = syn::parse_macro_input!(input as syn::DeriveInput);
 
     // Build the trait implementation.
     impl_hello_macro(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// Attribute-like macro: `#[route(GET, "/users/:id")]` on a function records
+// the route in `hello_macro::routes()`.
+#[proc_macro_attribute]
+pub fn route(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = syn::parse_macro_input!(attr as route::RouteArgs);
+    let function = syn::parse_macro_input!(item as syn::ItemFn);
+
+    route::impl_route(args, function)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// Function-like macro: `sql!(UserRow = SELECT id: u32 FROM users)` checks the
+// query at compile time and emits a `UserRow` struct with one field per column.
+#[proc_macro]
+pub fn sql(input: TokenStream) -> TokenStream {
+    let query = syn::parse_macro_input!(input as sql::Query);
+
+    sql::impl_sql(query).into()
 }
 
-fn impl_hello_macro(ast: &syn::DeriveInput) -> TokenStream {
-    let name = &ast.ident;
+fn impl_hello_macro(ast: &syn::DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &ast.ident;
+
+    // `split_for_impl` hands back the pieces of `<T: Bound>` and the where
+    // clause in the positions `impl<...> Trait for Type<...> where ...` expects,
+    // so generic types get a valid impl too.
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let mut name = None;
+    let mut greeting = None;
+    for attr in ast
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("hello"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else if meta.path.is_ident("greeting") {
+                greeting = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported hello attribute, expected `name` or `greeting`"))
+            }
+        })?;
+    }
+
+    let name = match name {
+        Some(name) if name.value().is_empty() => {
+            return Err(syn::Error::new(name.span(), "name must not be empty"));
+        }
+        Some(name) => name.value(),
+        None => ident.to_string(),
+    };
+    let greeting = match greeting {
+        Some(template) => render_greeting(&template.value(), &name)
+            .map_err(|message| syn::Error::new(template.span(), message))?,
+        None => render_greeting(DEFAULT_GREETING, &name).expect("default greeting is valid"),
+    };
+
+    // Generic types have no single concrete type to register, so only
+    // non-generic ones show up in `hello_macro::registry()`.
+    let registration = ast.generics.params.is_empty().then(|| {
+        quote! {
+            ::hello_macro::__private::inventory::submit! {
+                ::hello_macro::Registration {
+                    type_name: stringify!(#ident),
+                    greeting: <#ident as ::hello_macro::HelloMacro>::greeting,
+                }
+            }
+        }
+    });
+
     let generated = quote! {
-        impl HelloMacro for #name {
-            fn hello_macro() {
-                println!("Hello, Macro! My name is {}!", stringify!(#name));
+        impl #impl_generics ::hello_macro::HelloMacro for #ident #ty_generics #where_clause {
+            fn greeting() -> ::std::string::String {
+                ::std::string::String::from(#greeting)
+            }
+
+            fn type_name() -> &'static str {
+                stringify!(#ident)
             }
         }
+
+        #registration
     };
-    generated.into()
+    Ok(generated)
+}
+
+// Fills in `{name}` in a greeting template. Anything else in braces is an
+// error, and `{{`/`}}` escape literal braces just like in `format!`.
+fn render_greeting(template: &str, name: &str) -> Result<String, String> {
+    let mut rendered = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                rendered.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                rendered.push('}');
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        closed = true;
+                        break;
+                    }
+                    placeholder.push(c);
+                }
+                match placeholder.as_str() {
+                    _ if !closed => {
+                        return Err(String::from(
+                            "unmatched `{` in greeting, use `{{` to escape it",
+                        ));
+                    }
+                    "name" => rendered.push_str(name),
+                    other => {
+                        return Err(format!(
+                            "unknown placeholder `{{{other}}}` in greeting, only `{{name}}` is supported"
+                        ));
+                    }
+                }
+            }
+            '}' => {
+                return Err(String::from(
+                    "unmatched `}` in greeting, use `}}` to escape it",
+                ));
+            }
+            c => rendered.push(c),
+        }
+    }
+
+    Ok(rendered)
 }
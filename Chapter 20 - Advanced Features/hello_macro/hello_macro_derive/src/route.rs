@@ -0,0 +1,93 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{FnArg, Ident, ItemFn, LitStr, Pat, Token};
+
+// The `GET, "/users/:id"` part of `#[route(GET, "/users/:id")]`.
+pub struct RouteArgs {
+    method: Ident,
+    path: LitStr,
+}
+
+impl Parse for RouteArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let method = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let path = input.parse()?;
+        // Allow a trailing comma, but nothing else after the path.
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(RouteArgs { method, path })
+    }
+}
+
+pub fn impl_route(args: RouteArgs, function: ItemFn) -> syn::Result<TokenStream2> {
+    let method = match args.method.to_string().as_str() {
+        "GET" => quote! { ::hello_macro::Method::Get },
+        "POST" => quote! { ::hello_macro::Method::Post },
+        "PUT" => quote! { ::hello_macro::Method::Put },
+        "PATCH" => quote! { ::hello_macro::Method::Patch },
+        "DELETE" => quote! { ::hello_macro::Method::Delete },
+        _ => {
+            return Err(syn::Error::new(
+                args.method.span(),
+                "unsupported method, expected one of GET, POST, PUT, PATCH or DELETE",
+            ));
+        }
+    };
+
+    let path = args.path.value();
+    if !path.starts_with('/') {
+        return Err(syn::Error::new(
+            args.path.span(),
+            "route path must start with `/`",
+        ));
+    }
+
+    // Every `:param` segment has to be filled in by one of the function's
+    // arguments, otherwise the handler could never see it.
+    let arguments: Vec<String> = function
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            FnArg::Typed(typed) => match &*typed.pat {
+                Pat::Ident(pat) => Some(pat.ident.to_string()),
+                _ => None,
+            },
+            FnArg::Receiver(_) => None,
+        })
+        .collect();
+    for segment in path.split('/') {
+        if let Some(param) = segment.strip_prefix(':') {
+            if param.is_empty() {
+                return Err(syn::Error::new(
+                    args.path.span(),
+                    "route parameters need a name, e.g. `:id`",
+                ));
+            }
+            if !arguments.iter().any(|argument| argument == param) {
+                return Err(syn::Error::new(
+                    args.path.span(),
+                    format!("route parameter `:{param}` has no matching function argument"),
+                ));
+            }
+        }
+    }
+
+    // The function itself comes out untouched, we only record where it lives.
+    let name = &function.sig.ident;
+    let generated = quote! {
+        #function
+
+        ::hello_macro::__private::inventory::submit! {
+            ::hello_macro::Route {
+                method: #method,
+                path: #path,
+                handler: concat!(module_path!(), "::", stringify!(#name)),
+            }
+        }
+    };
+    Ok(generated)
+}
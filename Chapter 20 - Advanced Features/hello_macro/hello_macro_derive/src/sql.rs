@@ -0,0 +1,176 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitInt, Token, Type, Visibility};
+
+mod kw {
+    syn::custom_keyword!(SELECT);
+    syn::custom_keyword!(FROM);
+    syn::custom_keyword!(WHERE);
+    syn::custom_keyword!(AND);
+    syn::custom_keyword!(LIMIT);
+}
+
+// The grammar `sql!` accepts:
+//
+// query     := [vis] Name "=" SELECT column ("," column)* FROM table
+//              [WHERE condition (AND condition)*] [LIMIT integer]
+// column    := ident ":" type
+// condition := ident ("=" | "!=" | "<" | "<=" | ">" | ">=") "?"
+pub struct Query {
+    vis: Visibility,
+    name: Ident,
+    columns: Vec<(Ident, Type)>,
+    table: Ident,
+    conditions: Vec<(Ident, &'static str)>,
+    limit: Option<LitInt>,
+}
+
+impl Parse for Query {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+
+        input.parse::<kw::SELECT>()?;
+        if input.peek(kw::FROM) {
+            return Err(input.error("SELECT needs at least one column"));
+        }
+        let mut columns: Vec<(Ident, Type)> = Vec::new();
+        loop {
+            let column: Ident = input.parse()?;
+            if columns.iter().any(|(existing, _)| *existing == column) {
+                return Err(syn::Error::new(
+                    column.span(),
+                    format!("column `{column}` is selected more than once"),
+                ));
+            }
+            input.parse::<Token![:]>()?;
+            let ty = input.parse()?;
+            columns.push((column, ty));
+
+            if input.parse::<Option<Token![,]>>()?.is_none() {
+                break;
+            }
+        }
+
+        input.parse::<kw::FROM>()?;
+        let table = input.parse()?;
+
+        let mut conditions = Vec::new();
+        if input.parse::<Option<kw::WHERE>>()?.is_some() {
+            loop {
+                let column = input.parse()?;
+                let operator = parse_operator(input)?;
+                input.parse::<Token![?]>()?;
+                conditions.push((column, operator));
+
+                if input.parse::<Option<kw::AND>>()?.is_none() {
+                    break;
+                }
+            }
+        }
+
+        let limit = match input.parse::<Option<kw::LIMIT>>()? {
+            Some(_) => {
+                let limit: LitInt = input.parse()?;
+                limit.base10_parse::<u64>()?;
+                Some(limit)
+            }
+            None => None,
+        };
+
+        if !input.is_empty() {
+            return Err(input.error("unexpected tokens after the end of the query"));
+        }
+
+        Ok(Query {
+            vis,
+            name,
+            columns,
+            table,
+            conditions,
+            limit,
+        })
+    }
+}
+
+fn parse_operator(input: ParseStream) -> syn::Result<&'static str> {
+    if input.peek(Token![==]) {
+        return Err(input.error("SQL compares with `=`, not `==`"));
+    }
+
+    // Two-character operators first so `<=` isn't read as `<` followed by `=`.
+    let lookahead = input.lookahead1();
+    if lookahead.peek(Token![!=]) {
+        input.parse::<Token![!=]>()?;
+        Ok("!=")
+    } else if lookahead.peek(Token![<=]) {
+        input.parse::<Token![<=]>()?;
+        Ok("<=")
+    } else if lookahead.peek(Token![>=]) {
+        input.parse::<Token![>=]>()?;
+        Ok(">=")
+    } else if lookahead.peek(Token![=]) {
+        input.parse::<Token![=]>()?;
+        Ok("=")
+    } else if lookahead.peek(Token![<]) {
+        input.parse::<Token![<]>()?;
+        Ok("<")
+    } else if lookahead.peek(Token![>]) {
+        input.parse::<Token![>]>()?;
+        Ok(">")
+    } else {
+        Err(lookahead.error())
+    }
+}
+
+pub fn impl_sql(query: Query) -> TokenStream2 {
+    let Query {
+        vis,
+        name,
+        columns,
+        table,
+        conditions,
+        limit,
+    } = query;
+
+    let column_names: Vec<String> = columns
+        .iter()
+        .map(|(column, _)| column.to_string())
+        .collect();
+    let fields = columns
+        .iter()
+        .map(|(column, ty)| quote! { pub #column: #ty });
+
+    // Rebuild a normalised query string for the driver to run.
+    let mut sql = format!("SELECT {} FROM {table}", column_names.join(", "));
+    if !conditions.is_empty() {
+        let conditions: Vec<String> = conditions
+            .iter()
+            .map(|(column, operator)| format!("{column} {operator} ?"))
+            .collect();
+        sql.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+    }
+    if let Some(limit) = &limit {
+        sql.push_str(&format!(" LIMIT {}", limit.base10_digits()));
+    }
+
+    let table = table.to_string();
+    let params = conditions.len();
+
+    quote! {
+        #[derive(Debug, Clone, PartialEq)]
+        #vis struct #name {
+            #(#fields,)*
+        }
+
+        impl #name {
+            pub const SQL: &'static str = #sql;
+            pub const TABLE: &'static str = #table;
+            pub const COLUMNS: &'static [&'static str] = &[#(#column_names),*];
+            // How many `?` placeholders need binding before running `SQL`.
+            pub const PARAMS: usize = #params;
+        }
+    }
+}
@@ -1,3 +1,62 @@
 pub trait HelloMacro {
-    fn hello_macro();
+    fn hello_macro() {
+        println!("{}", Self::greeting());
+    }
+
+    // The greeting `hello_macro` prints, handy for libraries and tests that
+    // don't want to write to stdout.
+    fn greeting() -> String;
+
+    fn type_name() -> &'static str;
+}
+
+// One entry per non-generic type that derives `HelloMacro`. The derive submits
+// these to a link-time collection, so no type has to be listed by hand.
+pub struct Registration {
+    pub type_name: &'static str,
+    pub greeting: fn() -> String,
+}
+
+inventory::collect!(Registration);
+
+// Every registered type across the whole program, sorted by type name so the
+// order doesn't depend on how the linker laid things out.
+pub fn registry() -> Vec<&'static Registration> {
+    let mut registrations: Vec<_> = inventory::iter::<Registration>.into_iter().collect();
+    registrations.sort_by_key(|registration| registration.type_name);
+    registrations
+}
+
+// HTTP methods accepted by `#[route(METHOD, "/path")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+}
+
+// Metadata recorded for every function annotated with `#[route(...)]`.
+#[derive(Debug)]
+pub struct Route {
+    pub method: Method,
+    pub path: &'static str,
+    // Full path of the annotated function, e.g. `my_app::users::show`.
+    pub handler: &'static str,
+}
+
+inventory::collect!(Route);
+
+// Every route in the program, sorted by path and then method.
+pub fn routes() -> Vec<&'static Route> {
+    let mut routes: Vec<_> = inventory::iter::<Route>.into_iter().collect();
+    routes.sort_by_key(|route| (route.path, route.method as u8));
+    routes
+}
+
+// Used by the code `hello_macro_derive` generates, not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use inventory;
 }
@@ -0,0 +1,91 @@
+use hello_macro::HelloMacro;
+use hello_macro_derive::HelloMacro;
+
+#[derive(HelloMacro)]
+struct Pancakes;
+
+#[derive(HelloMacro)]
+struct Wrapper<T> {
+    _value: T,
+}
+
+#[derive(HelloMacro)]
+struct Bounded<'a, T: Clone>
+where
+    T: std::fmt::Debug,
+{
+    _value: &'a T,
+}
+
+#[derive(HelloMacro)]
+#[hello(name = "Waffles")]
+enum Breakfast {
+    _Sweet,
+    _Savoury,
+}
+
+#[derive(HelloMacro)]
+#[hello(greeting = "Good morning, {name}! {{not a placeholder}}")]
+struct Crepes;
+
+#[test]
+fn default_greeting_uses_type_name() {
+    assert_eq!(Pancakes::greeting(), "Hello, Macro! My name is Pancakes!");
+    assert_eq!(Pancakes::type_name(), "Pancakes");
+}
+
+#[test]
+fn generic_types_implement_the_trait() {
+    assert_eq!(
+        Wrapper::<u8>::greeting(),
+        "Hello, Macro! My name is Wrapper!"
+    );
+    assert_eq!(Wrapper::<u8>::type_name(), "Wrapper");
+    assert_eq!(
+        Bounded::<'static, String>::greeting(),
+        "Hello, Macro! My name is Bounded!"
+    );
+}
+
+#[test]
+fn name_attribute_changes_greeting_but_not_type_name() {
+    assert_eq!(Breakfast::greeting(), "Hello, Macro! My name is Waffles!");
+    assert_eq!(Breakfast::type_name(), "Breakfast");
+}
+
+#[test]
+fn greeting_attribute_fills_in_template() {
+    assert_eq!(
+        Crepes::greeting(),
+        "Good morning, Crepes! {not a placeholder}"
+    );
+}
+
+#[test]
+fn hello_macro_prints_greeting() {
+    Pancakes::hello_macro();
+    Breakfast::hello_macro();
+}
+
+#[test]
+fn registry_lists_non_generic_types() {
+    let registered: Vec<_> = hello_macro::registry()
+        .iter()
+        .map(|registration| (registration.type_name, (registration.greeting)()))
+        .collect();
+
+    assert_eq!(
+        registered,
+        [
+            ("Breakfast", Breakfast::greeting()),
+            ("Crepes", Crepes::greeting()),
+            ("Pancakes", Pancakes::greeting()),
+        ]
+    );
+}
+
+#[test]
+fn misuse_fails_to_compile() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,56 @@
+use hello_macro::Method;
+use hello_macro_derive::route;
+
+#[route(GET, "/users")]
+fn list_users() -> Vec<&'static str> {
+    vec!["ferris", "corro"]
+}
+
+#[route(GET, "/users/:id")]
+fn show_user(id: u32) -> String {
+    format!("user {id}")
+}
+
+#[route(DELETE, "/users/:id")]
+fn delete_user(id: u32) -> bool {
+    id != 0
+}
+
+mod posts {
+    use hello_macro_derive::route;
+
+    #[route(POST, "/posts/:post_id/comments/:comment_id")]
+    pub fn reply(post_id: u64, comment_id: u64, body: &str) -> String {
+        format!("{post_id}/{comment_id}: {body}")
+    }
+}
+
+#[test]
+fn annotated_functions_still_work() {
+    assert_eq!(list_users(), ["ferris", "corro"]);
+    assert_eq!(show_user(7), "user 7");
+    assert!(delete_user(7));
+    assert_eq!(posts::reply(1, 2, "hi"), "1/2: hi");
+}
+
+#[test]
+fn routes_are_recorded_with_their_handlers() {
+    let routes: Vec<_> = hello_macro::routes()
+        .iter()
+        .map(|route| (route.method, route.path, route.handler))
+        .collect();
+
+    assert_eq!(
+        routes,
+        [
+            (
+                Method::Post,
+                "/posts/:post_id/comments/:comment_id",
+                "route::posts::reply"
+            ),
+            (Method::Get, "/users", "route::list_users"),
+            (Method::Get, "/users/:id", "route::show_user"),
+            (Method::Delete, "/users/:id", "route::delete_user"),
+        ]
+    );
+}
@@ -0,0 +1,41 @@
+use hello_macro_derive::sql;
+
+sql!(pub UserRow = SELECT id: u32, name: String FROM users);
+
+sql!(ActiveUser = SELECT id: u32, email: Option<String> FROM users WHERE active = ? AND age >= ? LIMIT 50);
+
+sql!(Count = SELECT total: u64 FROM orders WHERE total != ? AND placed_at < ?);
+
+#[test]
+fn emits_struct_with_one_field_per_column() {
+    let row = UserRow {
+        id: 1,
+        name: String::from("ferris"),
+    };
+
+    assert_eq!(row.clone(), row);
+    assert_eq!(UserRow::COLUMNS, ["id", "name"]);
+    assert_eq!(UserRow::TABLE, "users");
+}
+
+#[test]
+fn normalises_query_text() {
+    assert_eq!(UserRow::SQL, "SELECT id, name FROM users");
+    assert_eq!(
+        ActiveUser::SQL,
+        "SELECT id, email FROM users WHERE active = ? AND age >= ? LIMIT 50"
+    );
+    assert_eq!(
+        Count::SQL,
+        "SELECT total FROM orders WHERE total != ? AND placed_at < ?"
+    );
+}
+
+#[test]
+fn counts_placeholders() {
+    assert_eq!(UserRow::PARAMS, 0);
+    assert_eq!(ActiveUser::PARAMS, 2);
+
+    let user = ActiveUser { id: 2, email: None };
+    assert_eq!(user.email, None);
+}
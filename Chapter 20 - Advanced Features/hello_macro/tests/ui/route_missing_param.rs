@@ -0,0 +1,8 @@
+use hello_macro_derive::route;
+
+#[route(GET, "/users/:id")]
+fn show_user(user_id: u32) -> u32 {
+    user_id
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use hello_macro_derive::route;
+
+#[route(GET, "users")]
+fn list_users() {}
+
+fn main() {}
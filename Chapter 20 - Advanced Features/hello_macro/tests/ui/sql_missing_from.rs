@@ -0,0 +1,5 @@
+use hello_macro_derive::sql;
+
+sql!(UserRow = SELECT id: u32, name: String users);
+
+fn main() {}
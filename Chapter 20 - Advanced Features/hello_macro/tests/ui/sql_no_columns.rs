@@ -0,0 +1,5 @@
+use hello_macro_derive::sql;
+
+sql!(UserRow = SELECT FROM users);
+
+fn main() {}
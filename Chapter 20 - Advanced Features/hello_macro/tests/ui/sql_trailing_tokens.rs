@@ -0,0 +1,5 @@
+use hello_macro_derive::sql;
+
+sql!(UserRow = SELECT id: u32 FROM users LIMIT 10 OFFSET 5);
+
+fn main() {}
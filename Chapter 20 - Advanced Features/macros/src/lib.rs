@@ -0,0 +1,183 @@
+// Declarative macros with macro_rules!
+//
+// Each arm is tried in order, so the `[elem; n]` form has to come before the
+// comma-separated list: `1, 2, 3` fails to match `$elem:expr ;` and falls
+// through to the next arm.
+#[macro_export]
+macro_rules! my_vec {
+    () => {
+        ::std::vec::Vec::new()
+    };
+    ( $elem:expr ; $n:expr ) => {
+        {
+            let n = $n;
+            let mut temp_vec = ::std::vec::Vec::with_capacity(n);
+            // `resize` clones the element, just like `vec![elem; n]`.
+            temp_vec.resize(n, $elem);
+            temp_vec
+        }
+    };
+    ( $( $x:expr ),+ $(,)? ) => {
+        {
+            // We know exactly how many elements are coming, so allocate once
+            // up front instead of growing the vector as we push.
+            let mut temp_vec = ::std::vec::Vec::with_capacity($crate::__count!($( $x ),+));
+            $(
+                temp_vec.push($x);
+            )+
+            temp_vec
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! my_hashmap {
+    () => {
+        ::std::collections::HashMap::new()
+    };
+    ( $( $key:expr => $value:expr ),+ $(,)? ) => {
+        {
+            let mut temp_map = ::std::collections::HashMap::with_capacity($crate::__count!($( $key ),+));
+            $(
+                temp_map.insert($key, $value);
+            )+
+            temp_map
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! my_btreeset {
+    () => {
+        ::std::collections::BTreeSet::new()
+    };
+    ( $( $x:expr ),+ $(,)? ) => {
+        {
+            // A BTreeSet has no capacity to reserve, so just insert.
+            let mut temp_set = ::std::collections::BTreeSet::new();
+            $(
+                temp_set.insert($x);
+            )+
+            temp_set
+        }
+    };
+}
+
+// Counts the expressions it's given at compile time without evaluating them:
+// each one is swapped for `()` and we take the length of the resulting array.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __count {
+    ( $( $x:expr ),* ) => {
+        <[()]>::len(&[ $( $crate::__count!(@unit $x) ),* ])
+    };
+    ( @unit $x:expr ) => {
+        ()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeSet, HashMap};
+
+    #[test]
+    fn empty() {
+        let v: Vec<i32> = my_vec![];
+        assert!(v.is_empty());
+
+        let m: HashMap<&str, i32> = my_hashmap! {};
+        assert!(m.is_empty());
+
+        let s: BTreeSet<i32> = my_btreeset![];
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn single() {
+        assert_eq!(my_vec![1], vec![1]);
+        assert_eq!(my_hashmap! { "one" => 1 }, HashMap::from([("one", 1)]));
+        assert_eq!(my_btreeset![1], BTreeSet::from([1]));
+    }
+
+    #[test]
+    fn trailing_comma() {
+        assert_eq!(my_vec![1, 2, 3,], vec![1, 2, 3]);
+        assert_eq!(
+            my_hashmap! {
+                "one" => 1,
+                "two" => 2,
+            },
+            HashMap::from([("one", 1), ("two", 2)])
+        );
+        assert_eq!(my_btreeset![3, 1, 2,], BTreeSet::from([1, 2, 3]));
+    }
+
+    #[test]
+    fn nested() {
+        let grid = my_vec![my_vec![1, 2], my_vec![], my_vec![0; 3]];
+        assert_eq!(grid, vec![vec![1, 2], vec![], vec![0, 0, 0]]);
+
+        let by_parity = my_hashmap! {
+            "even" => my_btreeset![4, 2, 2],
+            "odd" => my_btreeset![1],
+        };
+        assert_eq!(by_parity["even"], BTreeSet::from([2, 4]));
+        assert_eq!(by_parity["odd"], BTreeSet::from([1]));
+    }
+
+    #[test]
+    fn repetition() {
+        assert_eq!(my_vec![7; 3], vec![7, 7, 7]);
+        assert_eq!(my_vec![String::from("hi"); 2], vec!["hi", "hi"]);
+
+        let none: Vec<u8> = my_vec![1; 0];
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn repetition_evaluates_element_once() {
+        let mut calls = 0;
+        let v = my_vec![
+            {
+                calls += 1;
+                'x'
+            };
+            4
+        ];
+
+        assert_eq!(v, vec!['x'; 4]);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn preallocates_exact_capacity() {
+        let v = my_vec![1, 2, 3, 4, 5];
+        assert_eq!(v.capacity(), 5);
+
+        let v = my_vec![0u8; 10];
+        assert_eq!(v.capacity(), 10);
+
+        let m = my_hashmap! { 1 => 'a', 2 => 'b', 3 => 'c' };
+        assert!(m.capacity() >= 3);
+    }
+
+    #[test]
+    fn duplicate_keys_keep_last_value() {
+        let m = my_hashmap! { "k" => 1, "k" => 2 };
+        assert_eq!(m.len(), 1);
+        assert_eq!(m["k"], 2);
+    }
+
+    #[test]
+    fn count_does_not_evaluate() {
+        let mut calls = 0;
+        let mut bump = || {
+            calls += 1;
+            calls
+        };
+
+        assert_eq!(crate::__count!(bump(), bump(), bump()), 3);
+        assert_eq!(crate::__count!(), 0);
+        assert_eq!(bump(), 1);
+    }
+}
@@ -1,34 +1,38 @@
 use hello_macro::HelloMacro;
+use macros::{my_btreeset, my_hashmap, my_vec};
 
 struct Pancakes;
 
 impl HelloMacro for Pancakes {
-    fn hello_macro() {
-        println!("Hello, Macro! My name is Pancakes!");
+    fn greeting() -> String {
+        String::from("Hello, Macro! My name is Pancakes!")
+    }
+
+    fn type_name() -> &'static str {
+        "Pancakes"
     }
 }
 
 fn main() {
-    // Declarative macro example with custom vec! implementation
-    let v = my_vec!([1, 2, 3]);
+    // Declarative macro examples with custom vec!, HashMap and BTreeSet macros
+    // (defined in lib.rs)
+    let v = my_vec![1, 2, 3];
     println!("{:?}", v);
 
+    let zeroes = my_vec![0; 5];
+    println!("{:?}", zeroes);
+
+    let scores = my_hashmap! {
+        "Blue" => 10,
+        "Yellow" => 50,
+    };
+    println!("{:?}", scores);
+
+    let letters = my_btreeset!['c', 'a', 'b', 'a'];
+    println!("{:?}", letters);
+
     // Procedural derive macro
     Pancakes::hello_macro();
 
     // Attribute-like macro example (for structs and enum)
 }
-
-// Declarative macros with macro_rules!
-#[macro_export]
-macro_rules! my_vec {
-    ( $( $x:expr ),* ) => {
-        {
-            let mut temp_vec = Vec::new();
-            $(
-                temp_vec.push($x);
-            )*
-            temp_vec
-        }
-    };
-}
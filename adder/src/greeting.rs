@@ -0,0 +1,435 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::OnceLock;
+
+// The language everything falls back to. A catalog must have
+// every key for it.
+const FALLBACK: &str = "en";
+
+const REQUIRED_KEYS: [&str; 9] = [
+    "hello",
+    "hello.friends.one",
+    "hello.friends.other",
+    "name",
+    "name.titled",
+    "title.mr",
+    "title.ms",
+    "title.mx",
+    "title.dr",
+];
+
+const PLACEHOLDERS: [&str; 3] = ["name", "count", "title"];
+
+// A title to greet someone by. Where it goes and what it looks
+// like depends on the language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Title {
+    Mr,
+    Ms,
+    Mx,
+    Dr,
+}
+
+impl Title {
+    fn key(self) -> &'static str {
+        match self {
+            Title::Mr => "title.mr",
+            Title::Ms => "title.ms",
+            Title::Mx => "title.mx",
+            Title::Dr => "title.dr",
+        }
+    }
+}
+
+// Who to greet and how. Build one with new() and the with_
+// methods, then render it with a Catalog, or with to_string()
+// for the built-in one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Greeting {
+    name: String,
+    locale: String,
+    title: Option<Title>,
+    friends: Option<u64>,
+}
+
+impl Greeting {
+    pub fn new(name: &str) -> Greeting {
+        Greeting {
+            name: name.to_string(),
+            locale: String::from(FALLBACK),
+            title: None,
+            friends: None,
+        }
+    }
+
+    // Takes a language ("fr") or a full locale ("fr-CA",
+    // "fr_FR.UTF-8"); only the language part is used.
+    pub fn in_locale(mut self, locale: &str) -> Greeting {
+        self.locale = language(locale);
+        self
+    }
+
+    pub fn with_title(mut self, title: Title) -> Greeting {
+        self.title = Some(title);
+        self
+    }
+
+    // Greets the person along with `count` friends.
+    pub fn with_friends(mut self, count: u64) -> Greeting {
+        self.friends = Some(count);
+        self
+    }
+}
+
+impl fmt::Display for Greeting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&Catalog::builtin().render(self))
+    }
+}
+
+// Greeting templates for any number of languages, loaded from
+// the format described in locales/greetings.catalog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Catalog {
+    // language -> key -> template
+    messages: HashMap<String, HashMap<String, String>>,
+}
+
+impl Catalog {
+    // The catalog that ships with adder, covering en, fr, es,
+    // de and ja.
+    pub fn builtin() -> &'static Catalog {
+        static BUILTIN: OnceLock<Catalog> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            Catalog::parse(include_str!("../locales/greetings.catalog"))
+                .expect("the built-in catalog is valid")
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Catalog, CatalogError> {
+        let text = fs::read_to_string(path).map_err(CatalogError::Io)?;
+        Catalog::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> Result<Catalog, CatalogError> {
+        let mut messages: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut section = None;
+
+        for (i, line) in text.lines().enumerate() {
+            let syntax = |message: String| CatalogError::Syntax {
+                line: i + 1,
+                message,
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let name = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| syntax(String::from("expected `]` after the language")))?;
+                let name = language(name);
+                if name.is_empty() {
+                    return Err(syntax(String::from("the language can't be empty")));
+                }
+                messages.entry(name.clone()).or_default();
+                section = Some(name);
+                continue;
+            }
+
+            let Some((key, template)) = line.split_once('=') else {
+                return Err(syntax(String::from("expected `key = template`")));
+            };
+            let (key, template) = (key.trim(), template.trim());
+            if key.is_empty() || key.contains(char::is_whitespace) {
+                return Err(syntax(format!("'{key}' is not a valid key")));
+            }
+            check_template(template).map_err(syntax)?;
+
+            let Some(section) = &section else {
+                return Err(syntax(String::from(
+                    "expected a [language] before the first message",
+                )));
+            };
+            let section = messages
+                .get_mut(section)
+                .expect("sections are created first");
+            if section
+                .insert(key.to_string(), template.to_string())
+                .is_some()
+            {
+                return Err(syntax(format!("'{key}' is defined twice")));
+            }
+        }
+
+        let fallback = messages.get(FALLBACK);
+        for key in REQUIRED_KEYS {
+            if !fallback.is_some_and(|messages| messages.contains_key(key)) {
+                return Err(CatalogError::MissingFallback(key.to_string()));
+            }
+        }
+        Ok(Catalog { messages })
+    }
+
+    // The languages the catalog has a section for, sorted.
+    pub fn languages(&self) -> Vec<&str> {
+        let mut languages: Vec<&str> = self.messages.keys().map(String::as_str).collect();
+        languages.sort_unstable();
+        languages
+    }
+
+    pub fn render(&self, greeting: &Greeting) -> String {
+        let lang = if self.messages.contains_key(&greeting.locale) {
+            greeting.locale.as_str()
+        } else {
+            FALLBACK
+        };
+
+        let name = match greeting.title {
+            Some(title) => fill(
+                self.lookup(lang, "name.titled"),
+                &greeting.name,
+                "",
+                self.lookup(lang, title.key()),
+            ),
+            None => fill(self.lookup(lang, "name"), &greeting.name, "", ""),
+        };
+
+        match greeting.friends {
+            Some(count) => {
+                let plural = |lang| format!("hello.friends.{}", plural_category(lang, count));
+                // A language without a separate form for this
+                // count uses its `other` form before English
+                let template = self
+                    .find(lang, &plural(lang))
+                    .or_else(|| self.find(lang, "hello.friends.other"))
+                    .unwrap_or_else(|| self.lookup(FALLBACK, &plural(FALLBACK)));
+                fill(template, &name, &count.to_string(), "")
+            }
+            None => fill(self.lookup(lang, "hello"), &name, "", ""),
+        }
+    }
+
+    fn find(&self, lang: &str, key: &str) -> Option<&str> {
+        Some(self.messages.get(lang)?.get(key)?.as_str())
+    }
+
+    // The key from the language, or else from the fallback
+    // language, which has them all.
+    fn lookup(&self, lang: &str, key: &str) -> &str {
+        self.find(lang, key)
+            .unwrap_or_else(|| &self.messages[FALLBACK][key])
+    }
+}
+
+// "fr-CA", "fr_FR.UTF-8" and "FR" are all "fr".
+fn language(locale: &str) -> String {
+    locale
+        .trim()
+        .split(['-', '_', '.', '@'])
+        .next()
+        .unwrap_or("")
+        .to_lowercase()
+}
+
+// The plural category of `count`, following the Unicode CLDR
+// rules for the languages the built-in catalog covers. Other
+// languages get the English rule.
+fn plural_category(lang: &str, count: u64) -> &'static str {
+    match lang {
+        "ja" => "other",
+        "fr" if count <= 1 => "one",
+        "fr" => "other",
+        _ if count == 1 => "one",
+        _ => "other",
+    }
+}
+
+fn check_template(template: &str) -> Result<(), String> {
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        let (brace, after) = (&rest[i..i + 1], &rest[i + 1..]);
+        if after.starts_with(brace) {
+            rest = &after[1..];
+            continue;
+        }
+        if brace == "}" {
+            return Err(String::from("unmatched `}`, write `}}` for a brace"));
+        }
+
+        let end = after
+            .find('}')
+            .ok_or_else(|| String::from("unclosed `{`, write `{{` for a brace"))?;
+        let placeholder = &after[..end];
+        if !PLACEHOLDERS.contains(&placeholder) {
+            return Err(format!(
+                "unknown placeholder {{{placeholder}}}, expected {{name}}, {{count}} or {{title}}"
+            ));
+        }
+        rest = &after[end + 1..];
+    }
+    Ok(())
+}
+
+// Fills in a template that check_template() accepted.
+fn fill(template: &str, name: &str, count: &str, title: &str) -> String {
+    let mut filled = String::with_capacity(template.len() + name.len());
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        filled.push_str(&rest[..i]);
+        let after = &rest[i + 1..];
+        if rest[i..].starts_with("{{") || rest[i..].starts_with("}}") {
+            filled.push_str(&rest[i..i + 1]);
+            rest = &after[1..];
+            continue;
+        }
+
+        let end = after.find('}').expect("templates are checked on load");
+        filled.push_str(match &after[..end] {
+            "name" => name,
+            "count" => count,
+            _ => title,
+        });
+        rest = &after[end + 1..];
+    }
+    filled.push_str(rest);
+    filled
+}
+
+#[derive(Debug)]
+pub enum CatalogError {
+    Io(io::Error),
+    // A line that isn't in the catalog format. Lines count
+    // from 1.
+    Syntax { line: usize, message: String },
+    // The fallback language doesn't have this key
+    MissingFallback(String),
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatalogError::Io(err) => write!(f, "can't read the catalog: {err}"),
+            CatalogError::Syntax { line, message } => write!(f, "line {line}: {message}"),
+            CatalogError::MissingFallback(key) => {
+                write!(f, "the [{FALLBACK}] section is missing '{key}'")
+            }
+        }
+    }
+}
+
+impl Error for CatalogError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CatalogError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINIMAL: &str = "\
+[en]
+hello = Hi {name}!
+hello.friends.one = Hi {name} +{count}
+hello.friends.other = Hi {name} +{count}s
+name = {name}
+name.titled = {title} {name}
+title.mr = Mr.
+title.ms = Ms.
+title.mx = Mx.
+title.dr = Dr.
+";
+
+    fn syntax_error(text: &str) -> (usize, String) {
+        match Catalog::parse(&format!("{MINIMAL}{text}")) {
+            Err(CatalogError::Syntax { line, message }) => (line - 10, message),
+            other => panic!("expected a syntax error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn locales_boil_down_to_a_language() {
+        assert_eq!(language("fr"), "fr");
+        assert_eq!(language("fr-CA"), "fr");
+        assert_eq!(language("de_DE.UTF-8"), "de");
+        assert_eq!(language(" JA "), "ja");
+        assert_eq!(language("sr@latin"), "sr");
+    }
+
+    #[test]
+    fn plural_categories() {
+        assert_eq!(plural_category("en", 0), "other");
+        assert_eq!(plural_category("en", 1), "one");
+        assert_eq!(plural_category("fr", 0), "one");
+        assert_eq!(plural_category("fr", 2), "other");
+        assert_eq!(plural_category("ja", 1), "other");
+        assert_eq!(plural_category("xx", 1), "one");
+    }
+
+    #[test]
+    fn braces_and_placeholders() {
+        let catalog = Catalog::parse(&format!("{MINIMAL}[x]\nhello = {{{{{{name}}}}}}\n")).unwrap();
+
+        assert_eq!(
+            catalog.render(&Greeting::new("Carol").in_locale("x")),
+            "{Carol}"
+        );
+        assert_eq!(fill("{name}{count}{title}", "a", "b", "c"), "abc");
+    }
+
+    #[test]
+    fn reports_bad_lines() {
+        assert_eq!(
+            syntax_error("[fr\n"),
+            (1, String::from("expected `]` after the language"))
+        );
+        assert_eq!(
+            syntax_error("[fr]\n\nhello\n"),
+            (3, String::from("expected `key = template`"))
+        );
+        assert_eq!(
+            syntax_error("[fr]\nhello = {nom}\n"),
+            (
+                2,
+                String::from("unknown placeholder {nom}, expected {name}, {count} or {title}")
+            )
+        );
+        assert_eq!(
+            syntax_error("[fr]\nhello = {name\n").1,
+            "unclosed `{`, write `{{` for a brace"
+        );
+        assert_eq!(
+            syntax_error("[fr]\nhello = a}\n").1,
+            "unmatched `}`, write `}}` for a brace"
+        );
+        assert_eq!(
+            syntax_error("[en]\nhello = again\n").1,
+            "'hello' is defined twice"
+        );
+        assert_eq!(syntax_error("[]\n").1, "the language can't be empty");
+
+        match Catalog::parse("hello = Hi\n") {
+            Err(CatalogError::Syntax { line: 1, message }) => {
+                assert_eq!(message, "expected a [language] before the first message")
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn english_must_be_complete() {
+        let partial = MINIMAL.replace("title.dr = Dr.\n", "");
+        let err = Catalog::parse(&partial).unwrap_err();
+
+        assert_eq!(err.to_string(), "the [en] section is missing 'title.dr'");
+    }
+}
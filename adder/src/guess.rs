@@ -0,0 +1,236 @@
+use std::error::Error;
+use std::fmt;
+use std::num::{IntErrorKind, ParseIntError};
+use std::str::FromStr;
+
+// From our guessing game. This custom struct was created to ensure
+// that the guess value was within range of 1-100.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Guess {
+    value: i32,
+}
+
+impl Guess {
+    pub const MIN: i32 = 1;
+    pub const MAX: i32 = 100;
+
+    // Convenience for values known to be in range. Panics with the
+    // GuessError message otherwise.
+    pub fn new(value: i32) -> Guess {
+        match Guess::try_new(value) {
+            Ok(guess) => guess,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    pub fn try_new(value: i32) -> Result<Guess, GuessError> {
+        if value < Guess::MIN {
+            Err(GuessError::TooLow {
+                value,
+                min: Guess::MIN,
+            })
+        } else if value > Guess::MAX {
+            Err(GuessError::TooHigh {
+                value,
+                max: Guess::MAX,
+            })
+        } else {
+            Ok(Guess { value })
+        }
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+}
+
+impl TryFrom<i32> for Guess {
+    type Error = GuessError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        Guess::try_new(value)
+    }
+}
+
+impl From<Guess> for i32 {
+    fn from(guess: Guess) -> i32 {
+        guess.value
+    }
+}
+
+impl fmt::Display for Guess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+// A value outside the range a Guess allows, along with the bound
+// it crossed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuessError {
+    TooLow { value: i32, min: i32 },
+    TooHigh { value: i32, max: i32 },
+}
+
+impl GuessError {
+    pub fn value(&self) -> i32 {
+        match *self {
+            GuessError::TooLow { value, .. } | GuessError::TooHigh { value, .. } => value,
+        }
+    }
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuessError::TooLow { value, min } => write!(
+                f,
+                "Guess value must be greater than or equal to {min}, got {value}."
+            ),
+            GuessError::TooHigh { value, max } => write!(
+                f,
+                "Guess value must be less than or equal to {max}, got {value}."
+            ),
+        }
+    }
+}
+
+impl Error for GuessError {}
+
+// Parsing text can go wrong in two ways: it isn't a number at all,
+// or it is but it's out of range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGuessError {
+    NotANumber(ParseIntError),
+    OutOfRange(GuessError),
+}
+
+impl fmt::Display for ParseGuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGuessError::NotANumber(err) => write!(f, "Guess is not a number: {err}"),
+            ParseGuessError::OutOfRange(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for ParseGuessError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseGuessError::NotANumber(err) => Some(err),
+            ParseGuessError::OutOfRange(err) => Some(err),
+        }
+    }
+}
+
+impl From<GuessError> for ParseGuessError {
+    fn from(err: GuessError) -> ParseGuessError {
+        ParseGuessError::OutOfRange(err)
+    }
+}
+
+impl FromStr for Guess {
+    type Err = ParseGuessError;
+
+    // Surrounding whitespace is ignored, so a line read from stdin
+    // can be parsed as is. A number too big for an i32 is still a
+    // number, just out of range, so it's reported as the closest
+    // i32 instead.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = match s.trim().parse::<i32>() {
+            Ok(value) => value,
+            Err(err) => match err.kind() {
+                IntErrorKind::PosOverflow => i32::MAX,
+                IntErrorKind::NegOverflow => i32::MIN,
+                _ => return Err(ParseGuessError::NotANumber(err)),
+            },
+        };
+        Ok(Guess::try_new(value)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_the_whole_range() {
+        assert_eq!(Guess::try_new(1).map(|g| g.value()), Ok(1));
+        assert_eq!(Guess::try_new(100).map(|g| g.value()), Ok(100));
+        assert_eq!(Guess::new(42).value(), 42);
+    }
+
+    #[test]
+    fn reports_which_bound_was_crossed() {
+        assert_eq!(
+            Guess::try_new(0),
+            Err(GuessError::TooLow { value: 0, min: 1 })
+        );
+        assert_eq!(
+            Guess::try_new(101),
+            Err(GuessError::TooHigh {
+                value: 101,
+                max: 100
+            })
+        );
+        assert_eq!(Guess::try_new(i32::MIN).unwrap_err().value(), i32::MIN);
+    }
+
+    #[test]
+    fn converts_from_i32() {
+        assert_eq!(Guess::try_from(7), Ok(Guess::new(7)));
+        assert!(Guess::try_from(-7).is_err());
+
+        let guess: Guess = 64.try_into().unwrap();
+        assert_eq!(i32::from(guess), 64);
+    }
+
+    #[test]
+    fn parse_errors_are_told_apart_from_range_errors() {
+        assert_eq!(" 50\n".parse(), Ok(Guess::new(50)));
+        assert!(matches!(
+            "fifty".parse::<Guess>(),
+            Err(ParseGuessError::NotANumber(_))
+        ));
+        assert_eq!(
+            "99999999999".parse::<Guess>(),
+            Err(ParseGuessError::OutOfRange(GuessError::TooHigh {
+                value: i32::MAX,
+                max: 100
+            }))
+        );
+        assert_eq!(
+            "-99999999999".parse::<Guess>(),
+            Err(ParseGuessError::OutOfRange(GuessError::TooLow {
+                value: i32::MIN,
+                min: 1
+            }))
+        );
+        assert_eq!(
+            "500".parse::<Guess>(),
+            Err(ParseGuessError::OutOfRange(GuessError::TooHigh {
+                value: 500,
+                max: 100
+            }))
+        );
+    }
+
+    #[test]
+    fn error_messages() {
+        assert_eq!(
+            "-3".parse::<Guess>().unwrap_err().to_string(),
+            "Guess value must be greater than or equal to 1, got -3."
+        );
+        assert_eq!(
+            "".parse::<Guess>().unwrap_err().to_string(),
+            "Guess is not a number: cannot parse integer from empty string"
+        );
+        assert!("x".parse::<Guess>().unwrap_err().source().is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than or equal to 1, got 0")]
+    fn new_still_panics() {
+        Guess::new(0);
+    }
+}
@@ -1,5 +1,15 @@
 #![allow(dead_code)]
 
+mod greeting;
+mod guess;
+
+// The arithmetic is shared with the workspaces chapter, so
+// there's one Int trait and one set of overflow rules.
+pub use add_two::{add_two, checked_add_two, saturating_add_two, wrapping_add_two};
+pub use arithmetic::{Int, add, add_big, checked_add, saturating_add, wrapping_add};
+pub use greeting::{Catalog, CatalogError, Greeting, Title};
+pub use guess::{Guess, GuessError, ParseGuessError};
+
 #[derive(Debug)]
 struct Rectangle {
     width: u32,
@@ -12,34 +22,10 @@ impl Rectangle {
     }
 }
 
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
-
-pub fn add_two(a: usize) -> usize {
-    a + 2
-}
-
+// The English greeting. Use Greeting for other languages,
+// titles and friends.
 pub fn greeting(name: &str) -> String {
-    format!("Hello {name}!")
-}
-
-// From our guessing game. This custom struct was created to ensure
-// that the guess value was within range of 1-100.
-pub struct Guess {
-    value: i32,
-}
-
-impl Guess {
-    pub fn new(value: i32) -> Guess {
-        if value < 1 {
-            panic!("Guess value must be greater than or equal to 1, got {value}.");
-        } else if value > 100 {
-            panic!("Guess value must be less than or equal to 100, got {value}.");
-        }
-
-        Guess { value }
-    }
+    Greeting::new(name).to_string()
 }
 
 #[cfg(test)]
@@ -0,0 +1,120 @@
+use adder::{Catalog, CatalogError, Greeting, Title, greeting};
+use std::path::Path;
+
+fn builtin() -> Catalog {
+    Catalog::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("locales/greetings.catalog")).unwrap()
+}
+
+#[test]
+fn ships_five_languages() {
+    assert_eq!(builtin().languages(), ["de", "en", "es", "fr", "ja"]);
+    assert_eq!(&builtin(), Catalog::builtin());
+}
+
+#[test]
+fn greets_in_each_language() {
+    let cases = [
+        ("en", "Hello Carol!"),
+        ("fr", "Bonjour Carol !"),
+        ("es", "¡Hola Carol!"),
+        ("de", "Hallo Carol!"),
+        ("ja", "こんにちは、Carolさん！"),
+    ];
+    for (locale, expected) in cases {
+        assert_eq!(
+            Greeting::new("Carol").in_locale(locale).to_string(),
+            expected
+        );
+    }
+    assert_eq!(greeting("Carol"), "Hello Carol!");
+}
+
+#[test]
+fn titles_go_where_each_language_puts_them() {
+    let cases = [
+        ("en", Title::Dr, "Hello Dr. Tanaka!"),
+        ("fr", Title::Ms, "Bonjour Mme Tanaka !"),
+        ("es", Title::Mr, "¡Hola Sr. Tanaka!"),
+        ("de", Title::Ms, "Hallo Frau Tanaka!"),
+        ("ja", Title::Mr, "こんにちは、Tanaka様！"),
+        ("ja", Title::Dr, "こんにちは、Tanaka先生！"),
+    ];
+    for (locale, title, expected) in cases {
+        let greeting = Greeting::new("Tanaka").in_locale(locale).with_title(title);
+        assert_eq!(greeting.to_string(), expected, "{locale}");
+    }
+}
+
+#[test]
+fn friends_follow_each_languages_plural_rules() {
+    let cases = [
+        ("en", 1, "Hello Ana and 1 friend!"),
+        ("en", 0, "Hello Ana and 0 friends!"),
+        ("fr", 0, "Bonjour Ana et 0 ami !"),
+        ("fr", 1, "Bonjour Ana et 1 ami !"),
+        ("fr", 2, "Bonjour Ana et 2 amis !"),
+        ("es", 1, "¡Hola Ana y 1 amigo!"),
+        ("es", 5, "¡Hola Ana y 5 amigos!"),
+        ("de", 1, "Hallo Ana und 1 Freund!"),
+        ("de", 3, "Hallo Ana und 3 Freunde!"),
+        ("ja", 1, "こんにちは、Anaさんとお友達1人！"),
+        ("ja", 4, "こんにちは、Anaさんとお友達4人！"),
+    ];
+    for (locale, count, expected) in cases {
+        let greeting = Greeting::new("Ana").in_locale(locale).with_friends(count);
+        assert_eq!(greeting.to_string(), expected, "{locale} {count}");
+    }
+}
+
+#[test]
+fn full_locales_and_unknown_languages() {
+    assert_eq!(
+        Greeting::new("Max").in_locale("de_AT.UTF-8").to_string(),
+        "Hallo Max!"
+    );
+    // No Portuguese, so English it is, plural rules included
+    assert_eq!(
+        Greeting::new("Rui")
+            .in_locale("pt-BR")
+            .with_friends(1)
+            .to_string(),
+        "Hello Rui and 1 friend!"
+    );
+}
+
+#[test]
+fn missing_keys_fall_back_to_english() {
+    // German has no title for Mx or Dr of its own
+    assert_eq!(
+        Greeting::new("Kim")
+            .in_locale("de")
+            .with_title(Title::Mx)
+            .to_string(),
+        "Hallo Mx. Kim!"
+    );
+
+    let catalog = Catalog::parse(&format!(
+        "{}\n[it]\nhello = Ciao {{name}}!\n",
+        include_str!("../locales/greetings.catalog")
+    ))
+    .unwrap();
+    let italian = Greeting::new("Luca").in_locale("it");
+    assert_eq!(catalog.render(&italian), "Ciao Luca!");
+    assert_eq!(
+        catalog.render(&italian.clone().with_friends(2)),
+        "Hello Luca and 2 friends!"
+    );
+    assert_eq!(
+        catalog.render(&italian.with_title(Title::Dr)),
+        "Ciao Dr. Luca!"
+    );
+}
+
+#[test]
+fn unreadable_catalogs() {
+    let err = Catalog::load(Path::new("no/such/catalog")).unwrap_err();
+    assert!(matches!(err, CatalogError::Io(_)));
+
+    let err = Catalog::parse("[fr]\nhello = Salut {name}\n").unwrap_err();
+    assert_eq!(err.to_string(), "the [en] section is missing 'hello'");
+}
@@ -0,0 +1,442 @@
+//! Color representations and the conversions between them.
+//!
+//! [`Rgb`] is the hub: every other representation, including the RYB
+//! [`PrimaryColor`], [`SecondaryColor`] and [`TertiaryColor`] enums, converts
+//! to and from it.
+
+use crate::kinds::{ParseColorError, PrimaryColor, SecondaryColor, TertiaryColor};
+use crate::wheel::WheelColor;
+use std::fmt;
+use std::str::FromStr;
+
+/// A color as red, green and blue channels from 0 to 255.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+/// A color as hue (degrees, 0 to 360), saturation and lightness (0 to 1).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub h: f64,
+    pub s: f64,
+    pub l: f64,
+}
+
+/// A color as cyan, magenta, yellow and key (black) inks, each from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cmyk {
+    pub c: f64,
+    pub m: f64,
+    pub y: f64,
+    pub k: f64,
+}
+
+impl Rgb {
+    pub const fn new(r: u8, g: u8, b: u8) -> Rgb {
+        Rgb { r, g, b }
+    }
+
+    pub fn to_hsl(self) -> Hsl {
+        Hsl::from(self)
+    }
+
+    pub fn to_cmyk(self) -> Cmyk {
+        Cmyk::from(self)
+    }
+}
+
+impl Hsl {
+    /// Creates an HSL color, wrapping the hue into `0..360` and clamping
+    /// saturation and lightness into `0..=1`.
+    pub fn new(h: f64, s: f64, l: f64) -> Hsl {
+        Hsl {
+            h: h.rem_euclid(360.0),
+            s: s.clamp(0.0, 1.0),
+            l: l.clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn to_rgb(self) -> Rgb {
+        Rgb::from(self)
+    }
+}
+
+impl Cmyk {
+    /// Creates a CMYK color, clamping every ink into `0..=1`.
+    pub fn new(c: f64, m: f64, y: f64, k: f64) -> Cmyk {
+        Cmyk {
+            c: c.clamp(0.0, 1.0),
+            m: m.clamp(0.0, 1.0),
+            y: y.clamp(0.0, 1.0),
+            k: k.clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn to_rgb(self) -> Rgb {
+        Rgb::from(self)
+    }
+}
+
+/// Formats the color as a lowercase hex triplet, e.g. `#ff8800`.
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// Parses a hex triplet like `#ff8800` or the short form `#f80`. The `#` is
+/// optional, since shells treat it as the start of a comment.
+impl FromStr for Rgb {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.trim();
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        let err = || ParseColorError::new(s, "hex");
+
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(err());
+        }
+        let channel = |digits: &str| u8::from_str_radix(digits, 16).map_err(|_| err());
+
+        match hex.len() {
+            6 => Ok(Rgb::new(
+                channel(&hex[0..2])?,
+                channel(&hex[2..4])?,
+                channel(&hex[4..6])?,
+            )),
+            // Each digit is doubled, so `f80` is `ff8800`.
+            3 => Ok(Rgb::new(
+                channel(&hex[0..1])? * 17,
+                channel(&hex[1..2])? * 17,
+                channel(&hex[2..3])? * 17,
+            )),
+            _ => Err(err()),
+        }
+    }
+}
+
+/// Formats the color like CSS does, e.g. `hsl(32, 100%, 50%)`, rounding to
+/// whole degrees and percentages.
+impl fmt::Display for Hsl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "hsl({:.0}, {:.0}%, {:.0}%)",
+            self.h,
+            self.s * 100.0,
+            self.l * 100.0
+        )
+    }
+}
+
+/// Formats the inks as percentages, e.g. `cmyk(0%, 47%, 100%, 0%)`.
+impl fmt::Display for Cmyk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cmyk({:.0}%, {:.0}%, {:.0}%, {:.0}%)",
+            self.c * 100.0,
+            self.m * 100.0,
+            self.y * 100.0,
+            self.k * 100.0
+        )
+    }
+}
+
+fn to_channel(value: f64) -> u8 {
+    (value * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+impl From<Rgb> for Hsl {
+    fn from(rgb: Rgb) -> Hsl {
+        let r = f64::from(rgb.r) / 255.0;
+        let g = f64::from(rgb.g) / 255.0;
+        let b = f64::from(rgb.b) / 255.0;
+
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+        let l = (max + min) / 2.0;
+
+        // Greys have no hue or saturation.
+        if delta == 0.0 {
+            return Hsl { h: 0.0, s: 0.0, l };
+        }
+
+        let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+
+        Hsl { h, s, l }
+    }
+}
+
+impl From<Hsl> for Rgb {
+    fn from(hsl: Hsl) -> Rgb {
+        let Hsl { h, s, l } = Hsl::new(hsl.h, hsl.s, hsl.l);
+
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let sector = h / 60.0;
+        let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+        let (r, g, b) = match sector as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = l - chroma / 2.0;
+
+        Rgb::new(to_channel(r + m), to_channel(g + m), to_channel(b + m))
+    }
+}
+
+impl From<Rgb> for Cmyk {
+    fn from(rgb: Rgb) -> Cmyk {
+        let r = f64::from(rgb.r) / 255.0;
+        let g = f64::from(rgb.g) / 255.0;
+        let b = f64::from(rgb.b) / 255.0;
+
+        let k = 1.0 - r.max(g).max(b);
+        // Pure black: every other ink is irrelevant.
+        if k == 1.0 {
+            return Cmyk {
+                c: 0.0,
+                m: 0.0,
+                y: 0.0,
+                k,
+            };
+        }
+
+        Cmyk {
+            c: (1.0 - r - k) / (1.0 - k),
+            m: (1.0 - g - k) / (1.0 - k),
+            y: (1.0 - b - k) / (1.0 - k),
+            k,
+        }
+    }
+}
+
+impl From<Cmyk> for Rgb {
+    fn from(cmyk: Cmyk) -> Rgb {
+        let Cmyk { c, m, y, k } = Cmyk::new(cmyk.c, cmyk.m, cmyk.y, cmyk.k);
+
+        Rgb::new(
+            to_channel((1.0 - c) * (1.0 - k)),
+            to_channel((1.0 - m) * (1.0 - k)),
+            to_channel((1.0 - y) * (1.0 - k)),
+        )
+    }
+}
+
+impl From<Hsl> for Cmyk {
+    fn from(hsl: Hsl) -> Cmyk {
+        Cmyk::from(Rgb::from(hsl))
+    }
+}
+
+impl From<Cmyk> for Hsl {
+    fn from(cmyk: Cmyk) -> Hsl {
+        Hsl::from(Rgb::from(cmyk))
+    }
+}
+
+// The RYB colors don't have an exact RGB equivalent, so these are the values
+// painters' color wheels commonly use for them.
+impl From<PrimaryColor> for Rgb {
+    fn from(color: PrimaryColor) -> Rgb {
+        match color {
+            PrimaryColor::Red => Rgb::new(0xFE, 0x27, 0x12),
+            PrimaryColor::Yellow => Rgb::new(0xFE, 0xFE, 0x33),
+            PrimaryColor::Blue => Rgb::new(0x02, 0x47, 0xFE),
+        }
+    }
+}
+
+impl From<SecondaryColor> for Rgb {
+    fn from(color: SecondaryColor) -> Rgb {
+        match color {
+            SecondaryColor::Orange => Rgb::new(0xFB, 0x99, 0x02),
+            SecondaryColor::Green => Rgb::new(0x66, 0xB0, 0x32),
+            SecondaryColor::Purple => Rgb::new(0x86, 0x01, 0xAF),
+        }
+    }
+}
+
+impl From<TertiaryColor> for Rgb {
+    fn from(color: TertiaryColor) -> Rgb {
+        match color {
+            TertiaryColor::RedOrange => Rgb::new(0xFD, 0x53, 0x08),
+            TertiaryColor::YellowOrange => Rgb::new(0xFA, 0xBC, 0x02),
+            TertiaryColor::YellowGreen => Rgb::new(0xD0, 0xEA, 0x2B),
+            TertiaryColor::BlueGreen => Rgb::new(0x03, 0x91, 0xCE),
+            TertiaryColor::BluePurple => Rgb::new(0x3D, 0x01, 0xA4),
+            TertiaryColor::RedPurple => Rgb::new(0xA7, 0x19, 0x4B),
+        }
+    }
+}
+
+impl From<WheelColor> for Rgb {
+    fn from(color: WheelColor) -> Rgb {
+        match color {
+            WheelColor::Primary(color) => Rgb::from(color),
+            WheelColor::Secondary(color) => Rgb::from(color),
+            WheelColor::Tertiary(color) => Rgb::from(color),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "expected {expected} ± {tolerance}, got {actual}"
+        );
+    }
+
+    fn assert_rgb_close(actual: Rgb, expected: Rgb) {
+        for (a, e) in [
+            (actual.r, expected.r),
+            (actual.g, expected.g),
+            (actual.b, expected.b),
+        ] {
+            assert!(a.abs_diff(e) <= 1, "expected {expected:?}, got {actual:?}");
+        }
+    }
+
+    // A spread of saturated, pastel, dark and grey colors.
+    const SAMPLES: [Rgb; 10] = [
+        Rgb::new(0, 0, 0),
+        Rgb::new(255, 255, 255),
+        Rgb::new(128, 128, 128),
+        Rgb::new(255, 0, 0),
+        Rgb::new(0, 255, 0),
+        Rgb::new(0, 0, 255),
+        Rgb::new(255, 136, 0),
+        Rgb::new(18, 52, 86),
+        Rgb::new(250, 235, 215),
+        Rgb::new(134, 1, 175),
+    ];
+
+    #[test]
+    fn displays_as_hex() {
+        assert_eq!(Rgb::new(255, 136, 0).to_string(), "#ff8800");
+        assert_eq!(Rgb::new(0, 0, 0).to_string(), "#000000");
+    }
+
+    #[test]
+    fn parses_hex() {
+        assert_eq!("#ff8800".parse(), Ok(Rgb::new(255, 136, 0)));
+        assert_eq!("FF8800".parse(), Ok(Rgb::new(255, 136, 0)));
+        assert_eq!("#f80".parse(), Ok(Rgb::new(255, 136, 0)));
+
+        for bad in ["", "#", "#ff880", "#gg8800", "#ff88000", "+f80"] {
+            assert_eq!(
+                bad.parse::<Rgb>().unwrap_err().to_string(),
+                format!("'{bad}' is not a hex color")
+            );
+        }
+    }
+
+    #[test]
+    fn displays_hsl_and_cmyk_like_css() {
+        let orange = Rgb::new(255, 136, 0);
+
+        assert_eq!(orange.to_hsl().to_string(), "hsl(32, 100%, 50%)");
+        assert_eq!(orange.to_cmyk().to_string(), "cmyk(0%, 47%, 100%, 0%)");
+    }
+
+    #[test]
+    fn known_hsl_values() {
+        let orange = Rgb::new(255, 136, 0).to_hsl();
+        assert_close(orange.h, 32.0, 0.01);
+        assert_close(orange.s, 1.0, 1e-9);
+        assert_close(orange.l, 0.5, 1e-9);
+
+        let grey = Rgb::new(128, 128, 128).to_hsl();
+        assert_eq!((grey.h, grey.s), (0.0, 0.0));
+        assert_close(grey.l, 0.502, 0.001);
+
+        assert_eq!(Hsl::new(240.0, 1.0, 0.5).to_rgb(), Rgb::new(0, 0, 255));
+        assert_eq!(Hsl::new(120.0, 1.0, 0.25).to_rgb(), Rgb::new(0, 128, 0));
+    }
+
+    #[test]
+    fn known_cmyk_values() {
+        let red = Rgb::new(255, 0, 0).to_cmyk();
+        assert_eq!((red.c, red.m, red.y, red.k), (0.0, 1.0, 1.0, 0.0));
+
+        let black = Rgb::new(0, 0, 0).to_cmyk();
+        assert_eq!((black.c, black.m, black.y, black.k), (0.0, 0.0, 0.0, 1.0));
+
+        assert_eq!(
+            Cmyk::new(0.0, 0.0, 0.0, 0.0).to_rgb(),
+            Rgb::new(255, 255, 255)
+        );
+    }
+
+    #[test]
+    fn rgb_hsl_round_trip() {
+        for rgb in SAMPLES {
+            assert_rgb_close(rgb.to_hsl().to_rgb(), rgb);
+        }
+    }
+
+    #[test]
+    fn rgb_cmyk_round_trip() {
+        for rgb in SAMPLES {
+            assert_rgb_close(rgb.to_cmyk().to_rgb(), rgb);
+        }
+    }
+
+    #[test]
+    fn hsl_round_trip_within_tolerance() {
+        for h in (0..360).step_by(15) {
+            let hsl = Hsl::new(f64::from(h), 0.8, 0.4);
+            let back = hsl.to_rgb().to_hsl();
+
+            // 8-bit channels lose a little precision on the way through.
+            assert_close(back.h, hsl.h, 1.0);
+            assert_close(back.s, hsl.s, 0.01);
+            assert_close(back.l, hsl.l, 0.01);
+        }
+    }
+
+    #[test]
+    fn cmyk_hsl_round_trip_within_tolerance() {
+        // CMYK isn't unique, so start from the normalised form conversions
+        // produce: as much black as possible, leaving one ink at zero.
+        let cmyk = Cmyk::new(0.2, 0.6, 0.0, 0.3);
+        let back = Cmyk::from(Hsl::from(cmyk));
+
+        assert_close(back.c, cmyk.c, 0.01);
+        assert_close(back.m, cmyk.m, 0.01);
+        assert_close(back.y, cmyk.y, 0.01);
+        assert_close(back.k, cmyk.k, 0.01);
+    }
+
+    #[test]
+    fn hsl_new_normalises() {
+        let hsl = Hsl::new(-30.0, 1.5, -0.2);
+        assert_eq!((hsl.h, hsl.s, hsl.l), (330.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn ryb_colors_map_to_rgb() {
+        assert_eq!(Rgb::from(PrimaryColor::Red), Rgb::new(254, 39, 18));
+        assert_eq!(Rgb::from(SecondaryColor::Green), Rgb::new(102, 176, 50));
+    }
+}
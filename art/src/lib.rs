@@ -3,13 +3,27 @@
 //! A library for modeling artistic concepts.
 
 // Re-export statements
+pub use self::color::{Cmyk, Hsl, Rgb};
+pub use self::kinds::ParseColorError;
 pub use self::kinds::PrimaryColor;
 pub use self::kinds::SecondaryColor;
-pub use self::utils::mix;
+pub use self::kinds::TertiaryColor;
+pub use self::palette::{ExportFormat, Palette};
+pub use self::utils::{MixError, mix, mix_tertiary, mix_weighted};
+pub use self::wheel::WheelColor;
+
+pub mod color;
+pub mod palette;
+pub mod svg;
+pub mod wheel;
 
 pub mod kinds {
+    use std::error::Error;
+    use std::fmt;
+    use std::str::FromStr;
+
     /// The primary colors according to the RYB color model.
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     pub enum PrimaryColor {
         Red,
         Yellow,
@@ -17,22 +31,219 @@ pub mod kinds {
     }
 
     /// The secondary colors according to the RYB color model.
-    #[derive(Debug)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     pub enum SecondaryColor {
         Orange,
         Green,
         Purple,
     }
+
+    /// The tertiary colors according to the RYB color model, each sitting
+    /// between a primary and a secondary color on the color wheel.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum TertiaryColor {
+        RedOrange,
+        YellowOrange,
+        YellowGreen,
+        BlueGreen,
+        BluePurple,
+        RedPurple,
+    }
+
+    // Color names are written in lowercase with words joined by hyphens,
+    // e.g. "red" or "red-orange".
+    impl fmt::Display for PrimaryColor {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let name = match self {
+                PrimaryColor::Red => "red",
+                PrimaryColor::Yellow => "yellow",
+                PrimaryColor::Blue => "blue",
+            };
+            f.write_str(name)
+        }
+    }
+
+    impl fmt::Display for SecondaryColor {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let name = match self {
+                SecondaryColor::Orange => "orange",
+                SecondaryColor::Green => "green",
+                SecondaryColor::Purple => "purple",
+            };
+            f.write_str(name)
+        }
+    }
+
+    impl fmt::Display for TertiaryColor {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let name = match self {
+                TertiaryColor::RedOrange => "red-orange",
+                TertiaryColor::YellowOrange => "yellow-orange",
+                TertiaryColor::YellowGreen => "yellow-green",
+                TertiaryColor::BlueGreen => "blue-green",
+                TertiaryColor::BluePurple => "blue-purple",
+                TertiaryColor::RedPurple => "red-purple",
+            };
+            f.write_str(name)
+        }
+    }
+
+    /// Returned when a string doesn't name a color of the expected kind.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ParseColorError {
+        input: String,
+        kind: &'static str,
+    }
+
+    impl ParseColorError {
+        pub(crate) fn new(input: &str, kind: &'static str) -> ParseColorError {
+            ParseColorError {
+                input: input.to_string(),
+                kind,
+            }
+        }
+    }
+
+    impl fmt::Display for ParseColorError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "'{}' is not a {} color", self.input, self.kind)
+        }
+    }
+
+    impl Error for ParseColorError {}
+
+    // Parsing ignores case and surrounding whitespace, and accepts spaces or
+    // underscores in place of hyphens, so "Red Orange" parses too.
+    pub(crate) fn normalise(name: &str) -> String {
+        name.trim().to_lowercase().replace([' ', '_'], "-")
+    }
+
+    impl FromStr for PrimaryColor {
+        type Err = ParseColorError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match normalise(s).as_str() {
+                "red" => Ok(PrimaryColor::Red),
+                "yellow" => Ok(PrimaryColor::Yellow),
+                "blue" => Ok(PrimaryColor::Blue),
+                _ => Err(ParseColorError::new(s, "primary")),
+            }
+        }
+    }
+
+    impl FromStr for SecondaryColor {
+        type Err = ParseColorError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match normalise(s).as_str() {
+                "orange" => Ok(SecondaryColor::Orange),
+                "green" => Ok(SecondaryColor::Green),
+                "purple" => Ok(SecondaryColor::Purple),
+                _ => Err(ParseColorError::new(s, "secondary")),
+            }
+        }
+    }
+
+    impl FromStr for TertiaryColor {
+        type Err = ParseColorError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match normalise(s).as_str() {
+                "red-orange" => Ok(TertiaryColor::RedOrange),
+                "yellow-orange" => Ok(TertiaryColor::YellowOrange),
+                "yellow-green" => Ok(TertiaryColor::YellowGreen),
+                "blue-green" => Ok(TertiaryColor::BlueGreen),
+                "blue-purple" => Ok(TertiaryColor::BluePurple),
+                "red-purple" => Ok(TertiaryColor::RedPurple),
+                _ => Err(ParseColorError::new(s, "tertiary")),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn display_and_parse_round_trip() {
+            for color in [PrimaryColor::Red, PrimaryColor::Yellow, PrimaryColor::Blue] {
+                assert_eq!(color.to_string().parse(), Ok(color));
+            }
+            for color in [
+                SecondaryColor::Orange,
+                SecondaryColor::Green,
+                SecondaryColor::Purple,
+            ] {
+                assert_eq!(color.to_string().parse(), Ok(color));
+            }
+            assert_eq!(TertiaryColor::BlueGreen.to_string(), "blue-green");
+            assert_eq!("blue-green".parse(), Ok(TertiaryColor::BlueGreen));
+        }
+
+        #[test]
+        fn parsing_is_forgiving_about_case_and_separators() {
+            assert_eq!(" RED\n".parse(), Ok(PrimaryColor::Red));
+            assert_eq!("Purple".parse(), Ok(SecondaryColor::Purple));
+            assert_eq!("Red Orange".parse(), Ok(TertiaryColor::RedOrange));
+            assert_eq!("yellow_green".parse(), Ok(TertiaryColor::YellowGreen));
+        }
+
+        #[test]
+        fn parse_errors_name_the_input_and_kind() {
+            let err = "green".parse::<PrimaryColor>().unwrap_err();
+            assert_eq!(err.to_string(), "'green' is not a primary color");
+
+            let err = "".parse::<SecondaryColor>().unwrap_err();
+            assert_eq!(err.to_string(), "'' is not a secondary color");
+        }
+    }
 }
 
 pub mod utils {
+    use crate::color::Rgb;
     use crate::kinds::*;
+    use crate::wheel::WheelColor;
+    use std::error::Error;
+    use std::fmt;
+
+    /// The ways mixing colors can fail.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum MixError {
+        /// The same color was provided twice.
+        SameColor(WheelColor),
+        /// The two colors don't combine into a color of the requested kind.
+        UnsupportedCombination(WheelColor, WheelColor),
+        /// There were no colors, or all of them had a weight of zero.
+        NothingToMix,
+        /// A weight was negative, infinite or NaN.
+        InvalidWeight(f64),
+    }
+
+    impl fmt::Display for MixError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                MixError::SameColor(color) => write!(f, "cannot mix {color} with itself"),
+                MixError::UnsupportedCombination(c1, c2) => {
+                    write!(f, "{c1} and {c2} do not mix into a new color")
+                }
+                MixError::NothingToMix => write!(f, "nothing to mix"),
+                MixError::InvalidWeight(weight) => {
+                    write!(
+                        f,
+                        "invalid weight {weight}, weights must be finite and non-negative"
+                    )
+                }
+            }
+        }
+    }
+
+    impl Error for MixError {}
 
     /// Combines two primary colors in equal amounts to create
     /// a secondary color.
-    pub fn mix(c1: PrimaryColor, c2: PrimaryColor) -> Result<SecondaryColor, &'static str> {
+    pub fn mix(c1: PrimaryColor, c2: PrimaryColor) -> Result<SecondaryColor, MixError> {
         if c1 == c2 {
-            return Err("same primary color provided twice");
+            return Err(MixError::SameColor(c1.into()));
         }
 
         match (c1, c2) {
@@ -43,7 +254,242 @@ pub mod utils {
             | (PrimaryColor::Yellow, PrimaryColor::Red) => Ok(SecondaryColor::Orange),
             (PrimaryColor::Blue, PrimaryColor::Yellow)
             | (PrimaryColor::Yellow, PrimaryColor::Blue) => Ok(SecondaryColor::Green),
-            _ => Err("not a valid combination"),
+            _ => Err(MixError::UnsupportedCombination(c1.into(), c2.into())),
+        }
+    }
+
+    /// Combines a primary color with a neighbouring secondary color to
+    /// create the tertiary color between them, e.g. red and orange make
+    /// red-orange.
+    ///
+    /// A primary and the secondary opposite it on the color wheel (red and
+    /// green, say) make a muddy brown rather than a tertiary color, so
+    /// that's an error.
+    pub fn mix_tertiary(
+        primary: PrimaryColor,
+        secondary: SecondaryColor,
+    ) -> Result<TertiaryColor, MixError> {
+        let primary = WheelColor::from(primary);
+        let secondary = WheelColor::from(secondary);
+
+        // Neighbours sit two places apart with the tertiary color between.
+        let between = if primary.rotate(2) == secondary {
+            primary.rotate(1)
+        } else if primary.rotate(-2) == secondary {
+            primary.rotate(-1)
+        } else {
+            return Err(MixError::UnsupportedCombination(primary, secondary));
+        };
+
+        match between {
+            WheelColor::Tertiary(color) => Ok(color),
+            _ => unreachable!("tertiary colors sit between primaries and secondaries"),
+        }
+    }
+
+    /// Mixes any number of colors, each paired with a weight saying how
+    /// much of it goes into the mix.
+    ///
+    /// The result is the weighted average of each RGB channel, which is how
+    /// light blends rather than paint, so blue and yellow give a grey-green
+    /// instead of a vivid green.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use art::{PrimaryColor, Rgb, mix_weighted};
+    ///
+    /// let pink = mix_weighted([
+    ///     (Rgb::from(PrimaryColor::Red), 1.0),
+    ///     (Rgb::new(255, 255, 255), 3.0),
+    /// ]);
+    /// assert_eq!(pink, Ok(Rgb::new(255, 201, 196)));
+    /// ```
+    pub fn mix_weighted<C: Into<Rgb>>(
+        colors: impl IntoIterator<Item = (C, f64)>,
+    ) -> Result<Rgb, MixError> {
+        let mut weighted = Vec::new();
+        for (color, weight) in colors {
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(MixError::InvalidWeight(weight));
+            }
+            weighted.push((color.into(), weight));
+        }
+
+        // Only the ratios matter, so scale everything down by the largest
+        // weight first. Huge weights would otherwise add up to infinity.
+        let largest = weighted
+            .iter()
+            .map(|&(_, weight)| weight)
+            .fold(0.0, f64::max);
+        if largest == 0.0 {
+            return Err(MixError::NothingToMix);
+        }
+
+        let mut total = 0.0;
+        let mut sums = [0.0; 3];
+        for (rgb, weight) in weighted {
+            let weight = weight / largest;
+            total += weight;
+            sums[0] += f64::from(rgb.r) * weight;
+            sums[1] += f64::from(rgb.g) * weight;
+            sums[2] += f64::from(rgb.b) * weight;
+        }
+
+        let [r, g, b] = sums.map(|sum| (sum / total).round() as u8);
+        Ok(Rgb::new(r, g, b))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn mixes_primaries_into_secondaries() {
+            assert_eq!(
+                mix(PrimaryColor::Red, PrimaryColor::Yellow),
+                Ok(SecondaryColor::Orange)
+            );
+            assert_eq!(
+                mix(PrimaryColor::Blue, PrimaryColor::Red),
+                Ok(SecondaryColor::Purple)
+            );
+            assert_eq!(
+                mix(PrimaryColor::Blue, PrimaryColor::Blue),
+                Err(MixError::SameColor(WheelColor::Primary(PrimaryColor::Blue)))
+            );
+        }
+
+        #[test]
+        fn mixes_primary_and_neighbouring_secondary() {
+            use PrimaryColor::*;
+            use SecondaryColor::*;
+
+            let mixes = [
+                (Red, Orange, TertiaryColor::RedOrange),
+                (Yellow, Orange, TertiaryColor::YellowOrange),
+                (Yellow, Green, TertiaryColor::YellowGreen),
+                (Blue, Green, TertiaryColor::BlueGreen),
+                (Blue, Purple, TertiaryColor::BluePurple),
+                (Red, Purple, TertiaryColor::RedPurple),
+            ];
+            for (primary, secondary, tertiary) in mixes {
+                assert_eq!(mix_tertiary(primary, secondary), Ok(tertiary));
+            }
+        }
+
+        #[test]
+        fn complementary_primary_and_secondary_do_not_mix() {
+            assert_eq!(
+                mix_tertiary(PrimaryColor::Red, SecondaryColor::Green),
+                Err(MixError::UnsupportedCombination(
+                    PrimaryColor::Red.into(),
+                    SecondaryColor::Green.into()
+                ))
+            );
+            assert_eq!(
+                mix_tertiary(PrimaryColor::Blue, SecondaryColor::Orange),
+                Err(MixError::UnsupportedCombination(
+                    PrimaryColor::Blue.into(),
+                    SecondaryColor::Orange.into()
+                ))
+            );
+        }
+
+        #[test]
+        fn equal_weights_average_channels() {
+            let mixed = mix_weighted([(Rgb::new(0, 0, 0), 1.0), (Rgb::new(255, 255, 255), 1.0)]);
+            assert_eq!(mixed, Ok(Rgb::new(128, 128, 128)));
+        }
+
+        #[test]
+        fn weights_are_relative() {
+            let colors = [Rgb::new(255, 0, 0), Rgb::new(0, 0, 255)];
+            let one_to_three = mix_weighted([(colors[0], 1.0), (colors[1], 3.0)]);
+            let two_to_six = mix_weighted([(colors[0], 2.0), (colors[1], 6.0)]);
+
+            assert_eq!(one_to_three, Ok(Rgb::new(64, 0, 191)));
+            assert_eq!(one_to_three, two_to_six);
+        }
+
+        #[test]
+        fn huge_weights_dont_overflow() {
+            let mixed = mix_weighted([
+                (Rgb::new(255, 0, 0), f64::MAX),
+                (Rgb::new(0, 0, 255), f64::MAX),
+            ]);
+            assert_eq!(mixed, Ok(Rgb::new(128, 0, 128)));
+        }
+
+        #[test]
+        fn mixes_any_number_of_ryb_colors() {
+            let mixed = mix_weighted([
+                (PrimaryColor::Red, 1.0),
+                (PrimaryColor::Yellow, 1.0),
+                (PrimaryColor::Blue, 1.0),
+            ]);
+            assert_eq!(mixed, Ok(Rgb::new(170, 121, 108)));
+
+            let single = mix_weighted([(SecondaryColor::Green, 0.5)]);
+            assert_eq!(single, Ok(Rgb::from(SecondaryColor::Green)));
+        }
+
+        #[test]
+        fn zero_weight_colors_are_ignored() {
+            let mixed = mix_weighted([(Rgb::new(10, 20, 30), 1.0), (Rgb::new(255, 255, 255), 0.0)]);
+            assert_eq!(mixed, Ok(Rgb::new(10, 20, 30)));
+        }
+
+        #[test]
+        fn rejects_empty_and_invalid_weights() {
+            assert_eq!(
+                mix_weighted(Vec::<(Rgb, f64)>::new()),
+                Err(MixError::NothingToMix)
+            );
+            assert_eq!(
+                mix_weighted([(Rgb::new(0, 0, 0), 0.0)]),
+                Err(MixError::NothingToMix)
+            );
+            assert_eq!(
+                mix_weighted([(Rgb::new(0, 0, 0), -1.0)]),
+                Err(MixError::InvalidWeight(-1.0))
+            );
+            assert!(matches!(
+                mix_weighted([(Rgb::new(0, 0, 0), f64::NAN)]),
+                Err(MixError::InvalidWeight(weight)) if weight.is_nan()
+            ));
+        }
+
+        #[test]
+        fn errors_describe_the_offending_colors() {
+            let err = mix(PrimaryColor::Red, PrimaryColor::Red).unwrap_err();
+            assert_eq!(err.to_string(), "cannot mix red with itself");
+
+            let err = mix_tertiary(PrimaryColor::Yellow, SecondaryColor::Purple).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "yellow and purple do not mix into a new color"
+            );
+        }
+
+        #[test]
+        fn errors_convert_into_boxed_errors() {
+            fn parse_and_mix(c1: &str, c2: &str) -> Result<SecondaryColor, Box<dyn Error>> {
+                Ok(mix(c1.parse()?, c2.parse()?)?)
+            }
+
+            assert_eq!(
+                parse_and_mix("red", "blue").unwrap(),
+                SecondaryColor::Purple
+            );
+            assert_eq!(
+                parse_and_mix("red", "red").unwrap_err().to_string(),
+                "cannot mix red with itself"
+            );
+            assert_eq!(
+                parse_and_mix("red", "pink").unwrap_err().to_string(),
+                "'pink' is not a primary color"
+            );
         }
     }
 }
@@ -1,15 +1,220 @@
-use art::kinds::PrimaryColor;
-use art::utils::mix;
+use art::wheel::{self, WheelColor};
+use art::{ExportFormat, MixError, Palette, Rgb, mix, mix_tertiary, mix_weighted};
+use std::env;
 use std::process;
 
+const USAGE: &str = "\
+usage: art <command> [arguments]
+
+commands:
+  mix <color> <color>
+      Mix two different colors. Two primaries make a secondary and a primary
+      with a neighbouring secondary makes a tertiary. A primary and the
+      secondary opposite it don't mix. Any other pair is blended as RGB.
+  wheel (--complement | --analogous | --triadic | --split-complementary) <color>
+      Find harmonious colors on the RYB color wheel.
+  convert --to (hex | rgb | hsl | cmyk) <color>
+      Convert a color to another representation.
+  palette (monochrome | complementary | triadic) <color> [--format css|gpl|hex|svg] [--steps N]
+      Generate a palette and print it in the chosen format. Monochrome
+      palettes have N shades, from 1 to 64 (5 by default).
+  help
+      Show this message.
+
+Colors are names on the color wheel (red, orange, red-orange, ...) or hex
+triplets like ff8800. wheel and palette work on the color wheel itself, so
+they only take names. Quote hex colors that start with '#', since most shells
+treat '#' as the start of a comment.";
+
+// Enough shades to tell apart, and few enough that a typo can't exhaust
+// memory.
+const MAX_STEPS: usize = 64;
+
+// Bad usage (unknown commands, missing arguments) exits with 2 and prints the
+// usage text. Well-formed commands with bad input, like an unknown color,
+// exit with 1.
+enum CliError {
+    Usage(String),
+    Invalid(String),
+}
+
 fn main() {
-    let red = PrimaryColor::Red;
-    let yellow = PrimaryColor::Yellow;
-    println!(
-        "{:?}",
-        mix(red, yellow).unwrap_or_else(|err| {
-            eprintln!("[ERROR] {err}");
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match run(&args) {
+        Ok(output) => print!("{output}"),
+        Err(CliError::Usage(message)) => {
+            eprintln!("[ERROR] {message}");
+            eprintln!();
+            eprintln!("{USAGE}");
+            process::exit(2);
+        }
+        Err(CliError::Invalid(message)) => {
+            eprintln!("[ERROR] {message}");
             process::exit(1);
-        })
-    );
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<String, CliError> {
+    let Some((command, rest)) = args.split_first() else {
+        return Err(CliError::Usage(String::from("no command given")));
+    };
+
+    match command.as_str() {
+        "mix" => run_mix(rest),
+        "wheel" => run_wheel(rest),
+        "convert" => run_convert(rest),
+        "palette" => run_palette(rest),
+        "help" | "--help" | "-h" => Ok(format!("{USAGE}\n")),
+        _ => Err(CliError::Usage(format!("unknown command '{command}'"))),
+    }
+}
+
+fn run_mix(args: &[String]) -> Result<String, CliError> {
+    let [first, second] = args else {
+        return Err(CliError::Usage(String::from(
+            "mix takes exactly two colors",
+        )));
+    };
+
+    // Stay within the RYB model when we can, so the result has a name.
+    if let (Ok(c1), Ok(c2)) = (first.parse::<WheelColor>(), second.parse::<WheelColor>()) {
+        let mixed = match (c1, c2) {
+            _ if c1 == c2 => Err(MixError::SameColor(c1)),
+            (WheelColor::Primary(p1), WheelColor::Primary(p2)) => mix(p1, p2).map(WheelColor::from),
+            (WheelColor::Primary(p), WheelColor::Secondary(s))
+            | (WheelColor::Secondary(s), WheelColor::Primary(p)) => {
+                mix_tertiary(p, s).map(WheelColor::from)
+            }
+            _ => {
+                return blend(Rgb::from(c1), Rgb::from(c2));
+            }
+        };
+        let mixed = mixed.map_err(|err| CliError::Invalid(err.to_string()))?;
+        return Ok(format!("{mixed} {}\n", Rgb::from(mixed)));
+    }
+
+    let (c1, c2) = (parse_color(first)?, parse_color(second)?);
+    if c1 == c2 {
+        return Err(CliError::Invalid(format!("cannot mix {c1} with itself")));
+    }
+    blend(c1, c2)
+}
+
+// Equal parts of each color, for mixes the RYB model has no name for.
+fn blend(c1: Rgb, c2: Rgb) -> Result<String, CliError> {
+    let mixed =
+        mix_weighted([(c1, 1.0), (c2, 1.0)]).map_err(|err| CliError::Invalid(err.to_string()))?;
+    Ok(format!("{mixed}\n"))
+}
+
+fn run_wheel(args: &[String]) -> Result<String, CliError> {
+    let [harmony, color] = args else {
+        return Err(CliError::Usage(String::from(
+            "wheel takes a harmony option and a color",
+        )));
+    };
+    // An unknown option is a usage error even if the color is bad too.
+    let harmony: fn(WheelColor) -> Vec<WheelColor> = match harmony.as_str() {
+        "--complement" => |color| vec![wheel::complement(color)],
+        "--analogous" => |color| wheel::analogous(color).to_vec(),
+        "--triadic" => |color| wheel::triadic(color).to_vec(),
+        "--split-complementary" => |color| wheel::split_complementary(color).to_vec(),
+        _ => {
+            return Err(CliError::Usage(format!("unknown wheel option '{harmony}'")));
+        }
+    };
+    let color = color
+        .parse::<WheelColor>()
+        .map_err(|err| CliError::Invalid(err.to_string()))?;
+
+    Ok(harmony(color)
+        .into_iter()
+        .map(|color| format!("{color} {}\n", Rgb::from(color)))
+        .collect())
+}
+
+fn run_convert(args: &[String]) -> Result<String, CliError> {
+    let [flag, target, color] = args else {
+        return Err(CliError::Usage(String::from(
+            "convert takes --to <format> and a color",
+        )));
+    };
+    if flag != "--to" {
+        return Err(CliError::Usage(format!("unknown convert option '{flag}'")));
+    }
+    let color = parse_color(color)?;
+
+    let converted = match target.as_str() {
+        "hex" => color.to_string(),
+        "rgb" => format!("rgb({}, {}, {})", color.r, color.g, color.b),
+        "hsl" => color.to_hsl().to_string(),
+        "cmyk" => color.to_cmyk().to_string(),
+        _ => {
+            return Err(CliError::Invalid(format!(
+                "'{target}' is not a color format, expected hex, rgb, hsl or cmyk"
+            )));
+        }
+    };
+    Ok(format!("{converted}\n"))
+}
+
+fn run_palette(args: &[String]) -> Result<String, CliError> {
+    let [kind, color, options @ ..] = args else {
+        return Err(CliError::Usage(String::from(
+            "palette takes a palette kind and a color",
+        )));
+    };
+    // As with wheel, usage errors come before complaints about the color.
+    let palette: fn(WheelColor, usize) -> Palette = match kind.as_str() {
+        "monochrome" => Palette::monochrome,
+        "complementary" => |color, _| Palette::complementary(color),
+        "triadic" => |color, _| Palette::triadic(color),
+        _ => {
+            return Err(CliError::Usage(format!("unknown palette kind '{kind}'")));
+        }
+    };
+
+    let mut format = ExportFormat::Hex;
+    let mut steps = 5;
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        let value = options
+            .next()
+            .ok_or_else(|| CliError::Usage(format!("missing value for '{option}'")))?;
+        match option.as_str() {
+            "--format" => {
+                format = value
+                    .parse::<ExportFormat>()
+                    .map_err(|err| CliError::Invalid(err.to_string()))?
+            }
+            "--steps" => {
+                steps = value
+                    .parse()
+                    .ok()
+                    .filter(|steps| (1..=MAX_STEPS).contains(steps))
+                    .ok_or_else(|| {
+                        CliError::Invalid(format!(
+                            "'{value}' is not a valid number of steps, expected 1 to {MAX_STEPS}"
+                        ))
+                    })?
+            }
+            _ => return Err(CliError::Usage(format!("unknown option '{option}'"))),
+        }
+    }
+    let color = color
+        .parse::<WheelColor>()
+        .map_err(|err| CliError::Invalid(err.to_string()))?;
+
+    Ok(palette(color, steps).export(format))
+}
+
+// A color wheel name or a hex triplet.
+fn parse_color(input: &str) -> Result<Rgb, CliError> {
+    input
+        .parse::<WheelColor>()
+        .map(Rgb::from)
+        .or_else(|_| input.parse::<Rgb>())
+        .map_err(|_| CliError::Invalid(format!("'{input}' is not a color name or hex triplet")))
 }
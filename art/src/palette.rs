@@ -0,0 +1,298 @@
+//! Named groups of colors and the file formats they can be exported to.
+
+use crate::color::{Hsl, Rgb};
+use crate::wheel::{self, WheelColor};
+use std::error::Error;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+/// An ordered list of named colors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    name: String,
+    colors: Vec<(String, Rgb)>,
+}
+
+/// The formats a [`Palette`] can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// CSS custom properties on `:root`.
+    Css,
+    /// A GIMP `.gpl` palette file.
+    Gpl,
+    /// One hex color per line.
+    Hex,
+    /// An SVG swatch sheet, see [`svg::swatches`](crate::svg::swatches).
+    Svg,
+}
+
+/// Returned when a string doesn't name an [`ExportFormat`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFormatError(String);
+
+impl fmt::Display for ParseFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' is not a palette format, expected css, gpl, hex or svg",
+            self.0
+        )
+    }
+}
+
+impl Error for ParseFormatError {}
+
+impl FromStr for ExportFormat {
+    type Err = ParseFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "css" => Ok(ExportFormat::Css),
+            "gpl" | "gimp" => Ok(ExportFormat::Gpl),
+            "hex" => Ok(ExportFormat::Hex),
+            "svg" => Ok(ExportFormat::Svg),
+            _ => Err(ParseFormatError(s.to_string())),
+        }
+    }
+}
+
+impl Palette {
+    /// Creates an empty palette.
+    pub fn new(name: impl Into<String>) -> Palette {
+        Palette {
+            name: name.into(),
+            colors: Vec::new(),
+        }
+    }
+
+    /// Adds a color to the end of the palette.
+    pub fn push(&mut self, name: impl Into<String>, color: impl Into<Rgb>) {
+        self.colors.push((name.into(), color.into()));
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn colors(&self) -> &[(String, Rgb)] {
+        &self.colors
+    }
+
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// `steps` shades of a single color, from dark to light. The hue and
+    /// saturation stay the same while lightness runs from 20% to 80%.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use art::{Palette, PrimaryColor};
+    ///
+    /// let blues = Palette::monochrome(PrimaryColor::Blue, 3);
+    /// let names: Vec<_> = blues.colors().iter().map(|(name, _)| name.as_str()).collect();
+    /// assert_eq!(names, ["blue-1", "blue-2", "blue-3"]);
+    /// ```
+    pub fn monochrome(base: impl Into<WheelColor>, steps: usize) -> Palette {
+        let base = base.into();
+        let Hsl { h, s, .. } = Rgb::from(base).to_hsl();
+        let mut palette = Palette::new(format!("{base} monochrome"));
+
+        for step in 0..steps {
+            // A single step has nothing to ramp between, so use the middle.
+            let t = if steps == 1 {
+                0.5
+            } else {
+                step as f64 / (steps - 1) as f64
+            };
+            let shade = Hsl::new(h, s, 0.2 + 0.6 * t);
+            palette.push(format!("{base}-{}", step + 1), shade);
+        }
+
+        palette
+    }
+
+    /// The color and its complement.
+    pub fn complementary(base: impl Into<WheelColor>) -> Palette {
+        let base = base.into();
+        Palette::from_wheel(
+            format!("{base} complementary"),
+            [base, wheel::complement(base)],
+        )
+    }
+
+    /// The color and the two colors a third of the wheel away from it.
+    pub fn triadic(base: impl Into<WheelColor>) -> Palette {
+        let base = base.into();
+        let [second, third] = wheel::triadic(base);
+        Palette::from_wheel(format!("{base} triadic"), [base, second, third])
+    }
+
+    fn from_wheel(name: String, colors: impl IntoIterator<Item = WheelColor>) -> Palette {
+        let mut palette = Palette::new(name);
+        for color in colors {
+            palette.push(color.to_string(), color);
+        }
+        palette
+    }
+
+    pub fn export(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Css => self.to_css(),
+            ExportFormat::Gpl => self.to_gpl(),
+            ExportFormat::Hex => self.to_hex_list(),
+            ExportFormat::Svg => crate::svg::swatches(self),
+        }
+    }
+
+    /// CSS custom properties, one per color, named after the color.
+    pub fn to_css(&self) -> String {
+        let mut css = String::from(":root {\n");
+        for (name, color) in &self.colors {
+            writeln!(css, "  --{}: {color};", css_identifier(name)).unwrap();
+        }
+        css.push_str("}\n");
+        css
+    }
+
+    /// A GIMP palette: a header followed by one `R G B name` row per color.
+    pub fn to_gpl(&self) -> String {
+        let mut gpl = format!("GIMP Palette\nName: {}\nColumns: 0\n#\n", self.name);
+        for (name, color) in &self.colors {
+            writeln!(gpl, "{:>3} {:>3} {:>3}\t{name}", color.r, color.g, color.b).unwrap();
+        }
+        gpl
+    }
+
+    /// Just the colors, one hex triplet per line.
+    pub fn to_hex_list(&self) -> String {
+        self.colors
+            .iter()
+            .map(|(_, color)| format!("{color}\n"))
+            .collect()
+    }
+}
+
+// Custom property names can't contain spaces or most punctuation, so keep
+// letters, digits, hyphens and underscores and turn anything else into `-`.
+fn css_identifier(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kinds::{PrimaryColor, SecondaryColor};
+
+    #[test]
+    fn complementary_pairs_color_with_its_opposite() {
+        let palette = Palette::complementary(PrimaryColor::Red);
+
+        assert_eq!(palette.name(), "red complementary");
+        assert_eq!(
+            palette.colors(),
+            [
+                (String::from("red"), Rgb::from(PrimaryColor::Red)),
+                (String::from("green"), Rgb::from(SecondaryColor::Green)),
+            ]
+        );
+    }
+
+    #[test]
+    fn triadic_has_three_colors() {
+        let palette = Palette::triadic(SecondaryColor::Orange);
+        let names: Vec<_> = palette
+            .colors()
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+
+        assert_eq!(names, ["orange", "green", "purple"]);
+    }
+
+    #[test]
+    fn monochrome_ramps_lightness_and_keeps_hue() {
+        let palette = Palette::monochrome(PrimaryColor::Blue, 4);
+        let base_hue = Rgb::from(PrimaryColor::Blue).to_hsl().h;
+
+        assert_eq!(palette.len(), 4);
+        let mut last_lightness = 0.0;
+        for (_, color) in palette.colors() {
+            let hsl = color.to_hsl();
+            assert!((hsl.h - base_hue).abs() < 1.0);
+            assert!(hsl.l > last_lightness);
+            last_lightness = hsl.l;
+        }
+    }
+
+    #[test]
+    fn monochrome_edge_cases() {
+        assert!(Palette::monochrome(PrimaryColor::Red, 0).is_empty());
+
+        let single = Palette::monochrome(PrimaryColor::Red, 1);
+        assert!((single.colors()[0].1.to_hsl().l - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn exports_css_custom_properties() {
+        let mut palette = Palette::new("brand");
+        palette.push("Primary Blue", Rgb::new(0, 102, 204));
+        palette.push("accent", Rgb::new(255, 136, 0));
+
+        assert_eq!(
+            palette.export(ExportFormat::Css),
+            ":root {\n  --primary-blue: #0066cc;\n  --accent: #ff8800;\n}\n"
+        );
+    }
+
+    #[test]
+    fn exports_gimp_palette() {
+        let palette = Palette::complementary(PrimaryColor::Red);
+
+        assert_eq!(
+            palette.export(ExportFormat::Gpl),
+            "GIMP Palette\n\
+             Name: red complementary\n\
+             Columns: 0\n\
+             #\n\
+             254  39  18\tred\n\
+             102 176  50\tgreen\n"
+        );
+    }
+
+    #[test]
+    fn exports_hex_list() {
+        let palette = Palette::triadic(PrimaryColor::Red);
+
+        assert_eq!(
+            palette.export(ExportFormat::Hex),
+            "#fe2712\n#fefe33\n#0247fe\n"
+        );
+        assert_eq!(Palette::new("empty").export(ExportFormat::Hex), "");
+    }
+
+    #[test]
+    fn parses_export_formats() {
+        assert_eq!("CSS".parse(), Ok(ExportFormat::Css));
+        assert_eq!("gimp".parse(), Ok(ExportFormat::Gpl));
+        assert_eq!("svg".parse(), Ok(ExportFormat::Svg));
+        assert_eq!(
+            "png".parse::<ExportFormat>().unwrap_err().to_string(),
+            "'png' is not a palette format, expected css, gpl, hex or svg"
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! Renders colors to standalone SVG documents, e.g. for embedding in docs.
+
+use crate::color::Rgb;
+use crate::palette::Palette;
+use std::fmt::Write;
+
+const SWATCH_SIZE: u32 = 100;
+const GAP: u32 = 10;
+// Room under each swatch for its name and hex value.
+const LABEL_HEIGHT: u32 = 40;
+
+/// A row of square swatches, one per palette color, each labelled with the
+/// color's name and hex value.
+///
+/// # Examples
+///
+/// ```
+/// use art::{Palette, PrimaryColor, svg};
+///
+/// let sheet = svg::swatches(&Palette::complementary(PrimaryColor::Red));
+/// assert!(sheet.starts_with("<svg"));
+/// assert!(sheet.contains(r##"fill="#fe2712""##));
+/// ```
+pub fn swatches(palette: &Palette) -> String {
+    let count = palette.len() as u32;
+    let width = GAP + count * (SWATCH_SIZE + GAP);
+    let height = GAP + SWATCH_SIZE + LABEL_HEIGHT;
+
+    let mut svg = open_svg(width, height);
+    writeln!(svg, "  <title>{}</title>", escape(palette.name())).unwrap();
+    for (i, (name, color)) in palette.colors().iter().enumerate() {
+        let x = GAP + i as u32 * (SWATCH_SIZE + GAP);
+        let text_x = x + SWATCH_SIZE / 2;
+        let name_y = GAP + SWATCH_SIZE + 16;
+        let hex_y = name_y + 16;
+
+        writeln!(svg, "  <g>").unwrap();
+        writeln!(
+            svg,
+            r##"    <rect x="{x}" y="{GAP}" width="{SWATCH_SIZE}" height="{SWATCH_SIZE}" fill="{color}" stroke="#000000" stroke-width="1"/>"##
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"    <text x="{text_x}" y="{name_y}" font-family="sans-serif" font-size="12" text-anchor="middle">{}</text>"#,
+            escape(name)
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"    <text x="{text_x}" y="{hex_y}" font-family="monospace" font-size="12" text-anchor="middle">{color}</text>"#
+        )
+        .unwrap();
+        writeln!(svg, "  </g>").unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// A rectangle filled with a left-to-right gradient through `colors`,
+/// spaced evenly. Returns `None` if there are no colors to draw.
+///
+/// The gradient's id is made from its colors, so several gradients can be
+/// embedded in one page without one filling another's rectangle.
+pub fn gradient(colors: &[Rgb], width: u32, height: u32) -> Option<String> {
+    let (&first, rest) = colors.split_first()?;
+    let id = gradient_id(colors);
+
+    let mut svg = open_svg(width, height);
+    svg.push_str("  <defs>\n");
+    writeln!(
+        svg,
+        r#"    <linearGradient id="{id}" x1="0%" y1="0%" x2="100%" y2="0%">"#
+    )
+    .unwrap();
+    if rest.is_empty() {
+        // A single color is a flat fill, but still drawn as a gradient so
+        // the document has the same shape either way.
+        write_stop(&mut svg, 0.0, first);
+        write_stop(&mut svg, 100.0, first);
+    } else {
+        let last = colors.len() - 1;
+        for (i, &color) in colors.iter().enumerate() {
+            write_stop(&mut svg, i as f64 * 100.0 / last as f64, color);
+        }
+    }
+    svg.push_str("    </linearGradient>\n");
+    svg.push_str("  </defs>\n");
+    writeln!(
+        svg,
+        r#"  <rect x="0" y="0" width="{width}" height="{height}" fill="url(#{id})"/>"#
+    )
+    .unwrap();
+    svg.push_str("</svg>\n");
+    Some(svg)
+}
+
+fn gradient_id(colors: &[Rgb]) -> String {
+    let mut id = String::from("gradient");
+    for color in colors {
+        write!(id, "-{}", color.to_string().trim_start_matches('#')).unwrap();
+    }
+    id
+}
+
+fn open_svg(width: u32, height: u32) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n"
+    )
+}
+
+fn write_stop(svg: &mut String, offset: f64, color: Rgb) {
+    // Trim "50.00" down to "50" so round offsets stay readable.
+    let offset = format!("{offset:.2}");
+    let offset = offset.trim_end_matches('0').trim_end_matches('.');
+    writeln!(
+        svg,
+        r#"      <stop offset="{offset}%" stop-color="{color}"/>"#
+    )
+    .unwrap();
+}
+
+// Palette names come from users, so escape anything that would break the
+// markup.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_markup_in_names() {
+        assert_eq!(
+            escape(r#"<b>"Tom" & 'Jerry'</b>"#),
+            "&lt;b&gt;&quot;Tom&quot; &amp; &apos;Jerry&apos;&lt;/b&gt;"
+        );
+
+        let mut palette = Palette::new("a < b");
+        palette.push("salt & pepper", Rgb::new(1, 2, 3));
+        let sheet = swatches(&palette);
+
+        assert!(sheet.contains("<title>a &lt; b</title>"));
+        assert!(sheet.contains(">salt &amp; pepper</text>"));
+    }
+
+    #[test]
+    fn empty_palette_is_still_a_document() {
+        let sheet = swatches(&Palette::new("empty"));
+
+        assert!(sheet.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="10""#));
+        assert!(!sheet.contains("<rect"));
+        assert!(sheet.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn gradient_needs_a_color() {
+        assert_eq!(gradient(&[], 100, 10), None);
+    }
+
+    #[test]
+    fn gradient_offsets_are_evenly_spaced() {
+        let colors = [
+            Rgb::new(0, 0, 0),
+            Rgb::new(1, 1, 1),
+            Rgb::new(2, 2, 2),
+            Rgb::new(3, 3, 3),
+        ];
+        let svg = gradient(&colors, 100, 10).unwrap();
+
+        for offset in [
+            r#"offset="0%""#,
+            r#"offset="33.33%""#,
+            r#"offset="66.67%""#,
+            r#"offset="100%""#,
+        ] {
+            assert!(svg.contains(offset), "missing {offset} in {svg}");
+        }
+    }
+
+    #[test]
+    fn gradient_ids_follow_the_colors() {
+        let red_to_blue = gradient(&[Rgb::new(255, 0, 0), Rgb::new(0, 0, 255)], 50, 50).unwrap();
+        let blue_to_red = gradient(&[Rgb::new(0, 0, 255), Rgb::new(255, 0, 0)], 50, 50).unwrap();
+
+        assert!(red_to_blue.contains(r#"id="gradient-ff0000-0000ff""#));
+        assert!(red_to_blue.contains(r#"fill="url(#gradient-ff0000-0000ff)""#));
+        assert!(blue_to_red.contains(r#"id="gradient-0000ff-ff0000""#));
+    }
+
+    #[test]
+    fn single_color_gradient_is_flat() {
+        let svg = gradient(&[Rgb::new(255, 0, 0)], 50, 50).unwrap();
+
+        assert_eq!(svg.matches(r##"stop-color="#ff0000""##).count(), 2);
+    }
+}
@@ -0,0 +1,205 @@
+//! The twelve-color RYB color wheel and the harmonies painters pick from it.
+
+use crate::kinds::{ParseColorError, PrimaryColor, SecondaryColor, TertiaryColor};
+use std::fmt;
+use std::str::FromStr;
+
+/// Any color that has a place on the RYB color wheel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WheelColor {
+    Primary(PrimaryColor),
+    Secondary(SecondaryColor),
+    Tertiary(TertiaryColor),
+}
+
+/// The wheel in order, starting at red and heading towards orange.
+pub const WHEEL: [WheelColor; 12] = [
+    WheelColor::Primary(PrimaryColor::Red),
+    WheelColor::Tertiary(TertiaryColor::RedOrange),
+    WheelColor::Secondary(SecondaryColor::Orange),
+    WheelColor::Tertiary(TertiaryColor::YellowOrange),
+    WheelColor::Primary(PrimaryColor::Yellow),
+    WheelColor::Tertiary(TertiaryColor::YellowGreen),
+    WheelColor::Secondary(SecondaryColor::Green),
+    WheelColor::Tertiary(TertiaryColor::BlueGreen),
+    WheelColor::Primary(PrimaryColor::Blue),
+    WheelColor::Tertiary(TertiaryColor::BluePurple),
+    WheelColor::Secondary(SecondaryColor::Purple),
+    WheelColor::Tertiary(TertiaryColor::RedPurple),
+];
+
+impl WheelColor {
+    /// Where the color sits on the wheel, from 0 (red) to 11 (red-purple).
+    pub fn position(self) -> usize {
+        WHEEL
+            .iter()
+            .position(|&color| color == self)
+            .expect("every wheel color is on the wheel")
+    }
+
+    /// The color `steps` places clockwise from this one. Negative steps go
+    /// anticlockwise.
+    pub fn rotate(self, steps: isize) -> WheelColor {
+        let position = (self.position() as isize + steps).rem_euclid(WHEEL.len() as isize);
+        WHEEL[position as usize]
+    }
+}
+
+impl fmt::Display for WheelColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WheelColor::Primary(color) => color.fmt(f),
+            WheelColor::Secondary(color) => color.fmt(f),
+            WheelColor::Tertiary(color) => color.fmt(f),
+        }
+    }
+}
+
+/// Parses the name of any color on the wheel, e.g. "blue" or "red-orange".
+impl FromStr for WheelColor {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse()
+            .map(WheelColor::Primary)
+            .or_else(|_| s.parse().map(WheelColor::Secondary))
+            .or_else(|_| s.parse().map(WheelColor::Tertiary))
+            .map_err(|_| ParseColorError::new(s, "wheel"))
+    }
+}
+
+impl From<PrimaryColor> for WheelColor {
+    fn from(color: PrimaryColor) -> WheelColor {
+        WheelColor::Primary(color)
+    }
+}
+
+impl From<SecondaryColor> for WheelColor {
+    fn from(color: SecondaryColor) -> WheelColor {
+        WheelColor::Secondary(color)
+    }
+}
+
+impl From<TertiaryColor> for WheelColor {
+    fn from(color: TertiaryColor) -> WheelColor {
+        WheelColor::Tertiary(color)
+    }
+}
+
+/// The color directly opposite on the wheel.
+///
+/// # Examples
+///
+/// ```
+/// use art::wheel::{self, WheelColor};
+/// use art::{PrimaryColor, SecondaryColor};
+///
+/// assert_eq!(
+///     wheel::complement(PrimaryColor::Red),
+///     WheelColor::Secondary(SecondaryColor::Green)
+/// );
+/// ```
+pub fn complement(color: impl Into<WheelColor>) -> WheelColor {
+    color.into().rotate(6)
+}
+
+/// The two neighbours on either side of the color.
+pub fn analogous(color: impl Into<WheelColor>) -> [WheelColor; 2] {
+    let color = color.into();
+    [color.rotate(-1), color.rotate(1)]
+}
+
+/// The two colors that form an equilateral triangle with the color.
+pub fn triadic(color: impl Into<WheelColor>) -> [WheelColor; 2] {
+    let color = color.into();
+    [color.rotate(4), color.rotate(8)]
+}
+
+/// The two neighbours of the color's complement.
+pub fn split_complementary(color: impl Into<WheelColor>) -> [WheelColor; 2] {
+    let color = color.into();
+    [color.rotate(5), color.rotate(7)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_wheel_color() {
+        for color in WHEEL {
+            assert_eq!(color.to_string().parse(), Ok(color));
+        }
+        assert_eq!(
+            "pink".parse::<WheelColor>().unwrap_err().to_string(),
+            "'pink' is not a wheel color"
+        );
+    }
+
+    #[test]
+    fn positions_round_trip() {
+        for (i, color) in WHEEL.into_iter().enumerate() {
+            assert_eq!(color.position(), i);
+        }
+    }
+
+    #[test]
+    fn complements_are_symmetric() {
+        for color in WHEEL {
+            assert_ne!(complement(color), color);
+            assert_eq!(complement(complement(color)), color);
+        }
+        assert_eq!(
+            complement(PrimaryColor::Blue),
+            WheelColor::Secondary(SecondaryColor::Orange)
+        );
+        assert_eq!(
+            complement(TertiaryColor::YellowGreen),
+            WheelColor::Tertiary(TertiaryColor::RedPurple)
+        );
+    }
+
+    #[test]
+    fn analogous_wraps_around_red() {
+        assert_eq!(
+            analogous(PrimaryColor::Red),
+            [
+                WheelColor::Tertiary(TertiaryColor::RedPurple),
+                WheelColor::Tertiary(TertiaryColor::RedOrange),
+            ]
+        );
+    }
+
+    #[test]
+    fn triadic_of_a_primary_is_the_other_primaries() {
+        assert_eq!(
+            triadic(PrimaryColor::Red),
+            [
+                WheelColor::Primary(PrimaryColor::Yellow),
+                WheelColor::Primary(PrimaryColor::Blue),
+            ]
+        );
+        assert_eq!(
+            triadic(SecondaryColor::Green),
+            [
+                WheelColor::Secondary(SecondaryColor::Purple),
+                WheelColor::Secondary(SecondaryColor::Orange),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_complementary_flanks_the_complement() {
+        assert_eq!(
+            split_complementary(PrimaryColor::Yellow),
+            [
+                WheelColor::Tertiary(TertiaryColor::BluePurple),
+                WheelColor::Tertiary(TertiaryColor::RedPurple),
+            ]
+        );
+        for color in WHEEL {
+            let [a, b] = split_complementary(color);
+            assert_eq!(analogous(complement(color)), [a, b]);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::process::{Command, Output};
+
+fn art(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_art"))
+        .args(args)
+        .output()
+        .expect("failed to run the art binary")
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+fn stderr(output: &Output) -> &str {
+    std::str::from_utf8(&output.stderr).unwrap()
+}
+
+#[test]
+fn mixes_primaries_into_a_secondary() {
+    let output = art(&["mix", "red", "blue"]);
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "purple #8601af\n");
+}
+
+#[test]
+fn mixes_primary_and_secondary_into_a_tertiary() {
+    let output = art(&["mix", "Orange", "red"]);
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "red-orange #fd5308\n");
+}
+
+#[test]
+fn blends_hex_colors() {
+    let output = art(&["mix", "#000000", "ffffff"]);
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "#808080\n");
+}
+
+#[test]
+fn mixing_a_color_with_itself_fails() {
+    let output = art(&["mix", "red", "red"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "[ERROR] cannot mix red with itself\n");
+
+    // The same goes for secondaries and hex colors
+    let output = art(&["mix", "orange", "orange"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "[ERROR] cannot mix orange with itself\n");
+
+    let output = art(&["mix", "#ff8800", "FF8800"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "[ERROR] cannot mix #ff8800 with itself\n");
+}
+
+#[test]
+fn blends_pairs_the_wheel_has_no_name_for() {
+    let output = art(&["mix", "orange", "green"]);
+
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with('#'));
+}
+
+#[test]
+fn mixing_complements_fails() {
+    let output = art(&["mix", "red", "green"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("red and green do not mix"));
+}
+
+#[test]
+fn wheel_complement() {
+    let output = art(&["wheel", "--complement", "orange"]);
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "blue #0247fe\n");
+}
+
+#[test]
+fn wheel_triadic_and_split_complementary() {
+    let output = art(&["wheel", "--triadic", "red"]);
+    assert_eq!(stdout(&output), "yellow #fefe33\nblue #0247fe\n");
+
+    let output = art(&["wheel", "--split-complementary", "yellow"]);
+    assert_eq!(stdout(&output), "blue-purple #3d01a4\nred-purple #a7194b\n");
+}
+
+#[test]
+fn converts_between_formats() {
+    let cases = [
+        ("hsl", "#ff8800", "hsl(32, 100%, 50%)\n"),
+        ("rgb", "ff8800", "rgb(255, 136, 0)\n"),
+        ("cmyk", "#f80", "cmyk(0%, 47%, 100%, 0%)\n"),
+        ("hex", "purple", "#8601af\n"),
+    ];
+
+    for (target, color, expected) in cases {
+        let output = art(&["convert", "--to", target, color]);
+
+        assert!(output.status.success(), "convert --to {target} {color}");
+        assert_eq!(stdout(&output), expected);
+    }
+}
+
+#[test]
+fn invalid_colors_exit_with_one() {
+    let output = art(&["convert", "--to", "hsl", "#ff88"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stderr(&output),
+        "[ERROR] '#ff88' is not a color name or hex triplet\n"
+    );
+
+    let output = art(&["wheel", "--complement", "pink"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn prints_palettes() {
+    let output = art(&["palette", "complementary", "red", "--format", "css"]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        ":root {\n  --red: #fe2712;\n  --green: #66b032;\n}\n"
+    );
+}
+
+#[test]
+fn palette_steps_must_be_in_range() {
+    let output = art(&["palette", "monochrome", "blue", "--steps", "64"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output).lines().count(), 64);
+
+    for steps in ["0", "65", "18446744073709551615"] {
+        let output = art(&["palette", "monochrome", "blue", "--steps", steps]);
+
+        assert_eq!(output.status.code(), Some(1), "--steps {steps}");
+        assert_eq!(
+            stderr(&output),
+            format!("[ERROR] '{steps}' is not a valid number of steps, expected 1 to 64\n")
+        );
+    }
+}
+
+#[test]
+fn bad_usage_exits_with_two_and_prints_usage() {
+    for args in [
+        &[][..],
+        &["paint"],
+        &["mix", "red"],
+        &["wheel", "--opposite", "red"],
+        &["wheel", "--opposite", "pink"],
+        &["convert", "--from", "hsl", "red"],
+        &["palette", "triadic", "red", "--steps"],
+        &["palette", "bogus", "pink"],
+        &["palette", "triadic", "pink", "--shades", "3"],
+    ] {
+        let output = art(args);
+
+        assert_eq!(output.status.code(), Some(2), "art {args:?}");
+        assert!(
+            stderr(&output).contains("usage: art <command>"),
+            "art {args:?}"
+        );
+        assert!(stdout(&output).is_empty());
+    }
+}
+
+#[test]
+fn help_goes_to_stdout() {
+    let output = art(&["help"]);
+
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("usage: art <command>"));
+}
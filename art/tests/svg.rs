@@ -0,0 +1,50 @@
+use art::{Palette, PrimaryColor, Rgb, SecondaryColor, svg};
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// Compares rendered SVG against a file in tests/snapshots. Run with
+// `UPDATE_SNAPSHOTS=1 cargo test` to rewrite the files after an intended
+// change to the output, then review the diff.
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(name);
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("couldn't read snapshot {}: {err}", path.display()));
+    assert_eq!(actual, expected, "{name} doesn't match its snapshot");
+}
+
+#[test]
+fn triadic_swatches() {
+    let palette = Palette::triadic(PrimaryColor::Red);
+
+    assert_snapshot("triadic_red_swatches.svg", &svg::swatches(&palette));
+}
+
+#[test]
+fn monochrome_swatches() {
+    let palette = Palette::monochrome(SecondaryColor::Green, 4);
+
+    assert_snapshot("monochrome_green_swatches.svg", &svg::swatches(&palette));
+}
+
+#[test]
+fn primary_gradient() {
+    let colors = [
+        Rgb::from(PrimaryColor::Red),
+        Rgb::from(PrimaryColor::Yellow),
+        Rgb::from(PrimaryColor::Blue),
+    ];
+
+    assert_snapshot(
+        "primary_gradient.svg",
+        &svg::gradient(&colors, 300, 40).unwrap(),
+    );
+}
@@ -0,0 +1,116 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+// Each level sets how big the range of secret numbers is
+// and how many attempts you get. Hard gives exactly enough
+// attempts to always win by halving the range each time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn range(self) -> RangeInclusive<i32> {
+        match self {
+            Difficulty::Easy => 1..=20,
+            Difficulty::Normal => 1..=100,
+            Difficulty::Hard => 1..=1000,
+        }
+    }
+
+    pub fn max_attempts(self) -> u32 {
+        match self {
+            Difficulty::Easy => 6,
+            Difficulty::Normal => 8,
+            Difficulty::Hard => 10,
+        }
+    }
+
+    // Winning is worth 10 points for every attempt you had left
+    // over, counting the winning one, times a bonus for harder
+    // levels. Losing scores nothing.
+    pub fn score(self, attempts: u32) -> u32 {
+        let multiplier = match self {
+            Difficulty::Easy => 1,
+            Difficulty::Normal => 2,
+            Difficulty::Hard => 5,
+        };
+        let left_over = (self.max_attempts() + 1).saturating_sub(attempts);
+        10 * multiplier * left_over
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDifficultyError(String);
+
+impl fmt::Display for ParseDifficultyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' is not a difficulty, expected easy, normal or hard",
+            self.0
+        )
+    }
+}
+
+impl Error for ParseDifficultyError {}
+
+impl FromStr for Difficulty {
+    type Err = ParseDifficultyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(ParseDifficultyError(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_levels() {
+        assert_eq!("Hard".parse(), Ok(Difficulty::Hard));
+        assert_eq!(
+            "extreme".parse::<Difficulty>().unwrap_err().to_string(),
+            "'extreme' is not a difficulty, expected easy, normal or hard"
+        );
+    }
+
+    #[test]
+    fn enough_attempts_to_always_win() {
+        for level in [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard] {
+            let size = level.range().count() as u32;
+            // Binary search needs ceil(log2(size + 1)) attempts.
+            assert!(level.max_attempts() >= u32::BITS - size.leading_zeros());
+        }
+    }
+
+    #[test]
+    fn fewer_attempts_score_higher() {
+        assert_eq!(Difficulty::Normal.score(1), 160);
+        assert_eq!(Difficulty::Normal.score(8), 20);
+        assert_eq!(Difficulty::Hard.score(10), 50);
+        assert_eq!(Difficulty::Easy.score(7), 0);
+    }
+}
@@ -0,0 +1,325 @@
+use crate::difficulty::Difficulty;
+use crate::guess::Guess;
+use crate::solver::Solver;
+use rand::Rng;
+use std::cmp::Ordering;
+use std::io::{self, BufRead, Write};
+use std::ops::RangeInclusive;
+
+// What happened to a single guess. Only guesses that parse
+// and are in range use up an attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    TooSmall,
+    TooBig,
+    Win { attempts: u32, score: u32 },
+    InvalidInput,
+    OutOfRange,
+    // The last attempt was used up without finding the number
+    Lost { secret: i32 },
+}
+
+// The rules of one game, with no input or output of its own,
+// so it can be driven by a terminal, a test or a network
+// connection alike.
+#[derive(Debug, Clone)]
+pub struct Game {
+    difficulty: Difficulty,
+    secret_number: i32,
+    attempts: u32,
+    over: bool,
+    // Keeps track of what the guesses so far have ruled out,
+    // for hints
+    hints: Solver,
+}
+
+impl Game {
+    // Picks the secret number with `rng`. Pass a seeded RNG
+    // (e.g. StdRng::seed_from_u64) to get the same game every
+    // time.
+    pub fn new(difficulty: Difficulty, rng: &mut impl Rng) -> Game {
+        let secret_number = rng.gen_range(difficulty.range());
+        Game::with_secret(difficulty, secret_number)
+    }
+
+    pub fn with_secret(difficulty: Difficulty, secret_number: i32) -> Game {
+        Game {
+            difficulty,
+            secret_number,
+            attempts: 0,
+            over: false,
+            hints: Solver::new(difficulty.range()),
+        }
+    }
+
+    // The same game with `attempts` already used up, as if by
+    // earlier guesses. It's over straight away if that's all
+    // of them.
+    pub fn with_attempts_used(mut self, attempts: u32) -> Game {
+        self.attempts = attempts.min(self.difficulty.max_attempts());
+        self.over = self.attempts_left() == 0;
+        self
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn attempts_left(&self) -> u32 {
+        self.difficulty.max_attempts() - self.attempts
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.over
+    }
+
+    // The numbers the secret could still be, going by the
+    // answers to the guesses so far.
+    pub fn hint(&self) -> RangeInclusive<i32> {
+        self.hints.remaining()
+    }
+
+    // Takes a line of player input as is, surrounding
+    // whitespace included.
+    pub fn guess(&mut self, input: &str) -> Outcome {
+        match input.trim().parse() {
+            Ok(value) => self.guess_value(value),
+            Err(_) => Outcome::InvalidInput,
+        }
+    }
+
+    // Panics if the game is already over.
+    pub fn guess_value(&mut self, value: i32) -> Outcome {
+        assert!(!self.over, "the game is already over");
+
+        let Some(guess) = Guess::try_new(value, &self.difficulty.range()) else {
+            return Outcome::OutOfRange;
+        };
+        self.attempts += 1;
+
+        let ordering = guess.get_value().cmp(&self.secret_number);
+        self.hints.feedback(guess, ordering);
+        let outcome = match ordering {
+            Ordering::Equal => Outcome::Win {
+                attempts: self.attempts,
+                score: self.difficulty.score(self.attempts),
+            },
+            _ if self.attempts_left() == 0 => Outcome::Lost {
+                secret: self.secret_number,
+            },
+            Ordering::Less => Outcome::TooSmall,
+            Ordering::Greater => Outcome::TooBig,
+        };
+        self.over = matches!(outcome, Outcome::Win { .. } | Outcome::Lost { .. });
+        outcome
+    }
+}
+
+// The text version of the game, reading guesses from `input`
+// and writing everything the player sees to `output`. Returns
+// the final Win or Lost outcome.
+//
+// Running out of input before the game is over is an
+// UnexpectedEof error, and a game that's already over is
+// an InvalidInput error.
+pub fn play(game: &mut Game, input: impl BufRead, output: impl Write) -> io::Result<Outcome> {
+    run(game, input, output, false)
+}
+
+// Same as play(), but after every wrong guess the player is
+// told which numbers are still possible.
+pub fn play_with_hints(
+    game: &mut Game,
+    input: impl BufRead,
+    output: impl Write,
+) -> io::Result<Outcome> {
+    run(game, input, output, true)
+}
+
+fn run(
+    game: &mut Game,
+    mut input: impl BufRead,
+    mut output: impl Write,
+    hints: bool,
+) -> io::Result<Outcome> {
+    if game.is_over() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the game is already over",
+        ));
+    }
+    let range = game.difficulty().range();
+
+    writeln!(output, "Guess the number!")?;
+    writeln!(
+        output,
+        "It's between {} and {}, and you have {} attempts.",
+        range.start(),
+        range.end(),
+        game.attempts_left()
+    )?;
+
+    loop {
+        writeln!(output, "Please input your guess.")?;
+
+        let mut guess = String::new();
+        if input.read_line(&mut guess)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "input ended before the game did",
+            ));
+        }
+
+        let outcome = game.guess(&guess);
+        match outcome {
+            Outcome::InvalidInput => writeln!(output, "Please type a number!")?,
+            Outcome::OutOfRange => writeln!(
+                output,
+                "The number has to be between {} and {}.",
+                range.start(),
+                range.end()
+            )?,
+            Outcome::TooSmall | Outcome::TooBig => {
+                let answer = if outcome == Outcome::TooSmall {
+                    "Too small!"
+                } else {
+                    "Too big!"
+                };
+                writeln!(output, "You guessed: {}\n{answer}", guess.trim())?;
+                if hints {
+                    let hint = game.hint();
+                    writeln!(
+                        output,
+                        "Hint: it's between {} and {}.",
+                        hint.start(),
+                        hint.end()
+                    )?;
+                }
+            }
+            Outcome::Win { attempts, score } => {
+                writeln!(output, "You guessed: {}", guess.trim())?;
+                writeln!(output, "You win in {attempts} attempts! Score: {score}")?;
+                return Ok(outcome);
+            }
+            Outcome::Lost { secret } => {
+                writeln!(output, "You guessed: {}", guess.trim())?;
+                writeln!(output, "You lose! The number was {secret}.")?;
+                return Ok(outcome);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcomes_for_each_kind_of_guess() {
+        let mut game = Game::with_secret(Difficulty::Normal, 42);
+
+        assert_eq!(game.guess("fifty"), Outcome::InvalidInput);
+        assert_eq!(game.guess("101"), Outcome::OutOfRange);
+        assert_eq!(game.guess("99999999999"), Outcome::InvalidInput);
+        assert_eq!(game.attempts(), 0);
+
+        assert_eq!(game.guess("50\n"), Outcome::TooBig);
+        assert_eq!(game.guess(" 25 "), Outcome::TooSmall);
+        assert_eq!(game.attempts_left(), 6);
+        assert!(!game.is_over());
+
+        assert_eq!(
+            game.guess("42"),
+            Outcome::Win {
+                attempts: 3,
+                score: 120
+            }
+        );
+        assert!(game.is_over());
+    }
+
+    #[test]
+    fn last_wrong_guess_loses() {
+        let mut game = Game::with_secret(Difficulty::Easy, 20);
+        for guess in 1..6 {
+            assert_eq!(game.guess_value(guess), Outcome::TooSmall);
+        }
+
+        assert_eq!(game.guess_value(6), Outcome::Lost { secret: 20 });
+        assert!(game.is_over());
+    }
+
+    #[test]
+    fn winning_on_the_last_attempt_still_wins() {
+        let mut game = Game::with_secret(Difficulty::Easy, 6);
+        for guess in 1..6 {
+            game.guess_value(guess);
+        }
+
+        assert_eq!(
+            game.guess_value(6),
+            Outcome::Win {
+                attempts: 6,
+                score: 10
+            }
+        );
+    }
+
+    #[test]
+    fn carrying_over_used_attempts() {
+        let mut game = Game::with_secret(Difficulty::Easy, 20).with_attempts_used(5);
+        assert_eq!(game.attempts_left(), 1);
+        assert_eq!(game.guess_value(1), Outcome::Lost { secret: 20 });
+
+        let game = Game::with_secret(Difficulty::Easy, 20).with_attempts_used(99);
+        assert_eq!(game.attempts_left(), 0);
+        assert!(game.is_over());
+    }
+
+    #[test]
+    #[should_panic(expected = "the game is already over")]
+    fn no_guessing_after_the_game() {
+        let mut game = Game::with_secret(Difficulty::Easy, 1);
+        game.guess_value(1);
+        game.guess_value(1);
+    }
+
+    #[test]
+    fn running_out_of_input_is_an_error() {
+        let mut game = Game::with_secret(Difficulty::Hard, 500);
+        let err = play(&mut game, "100\n".as_bytes(), io::sink()).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn playing_a_finished_game_is_an_error() {
+        let mut game = Game::with_secret(Difficulty::Easy, 1);
+        game.guess_value(1);
+
+        let mut output = Vec::new();
+        let err = play(&mut game, "1\n".as_bytes(), &mut output).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn hints_follow_the_guesses() {
+        let mut game = Game::with_secret(Difficulty::Normal, 42);
+        assert_eq!(game.hint(), 1..=100);
+
+        game.guess_value(50);
+        game.guess_value(90);
+        game.guess_value(30);
+        assert_eq!(game.hint(), 31..=49);
+
+        let mut output = Vec::new();
+        play_with_hints(&mut game, "45\n42\n".as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Too big!\nHint: it's between 31 and 44.\n"));
+    }
+}
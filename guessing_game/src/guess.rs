@@ -0,0 +1,57 @@
+use std::ops::RangeInclusive;
+
+// Create custom struct to avoid having to perform
+// validation on value (ensuring it's in the game's range)
+// in every single function. If instead we receive
+// a Guess struct, we know for a fact the value is
+// valid with how the new() function is defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Guess {
+    value: i32,
+}
+
+impl Guess {
+    pub fn new(value: i32, range: &RangeInclusive<i32>) -> Guess {
+        match Guess::try_new(value, range) {
+            Some(guess) => guess,
+            None => panic!(
+                "The number has to be between {} and {}.",
+                range.start(),
+                range.end()
+            ),
+        }
+    }
+
+    // Same check as new(), for callers that want to handle
+    // a bad value instead of panicking
+    pub fn try_new(value: i32, range: &RangeInclusive<i32>) -> Option<Guess> {
+        if range.contains(&value) {
+            Some(Guess { value })
+        } else {
+            None
+        }
+    }
+
+    pub fn get_value(&self) -> i32 {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_values_in_range() {
+        assert_eq!(Guess::new(1, &(1..=10)).get_value(), 1);
+        assert_eq!(Guess::new(10, &(1..=10)).get_value(), 10);
+        assert_eq!(Guess::try_new(11, &(1..=10)), None);
+        assert_eq!(Guess::try_new(0, &(1..=10)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "The number has to be between 1 and 1000.")]
+    fn panics_outside_range() {
+        Guess::new(1001, &(1..=1000));
+    }
+}
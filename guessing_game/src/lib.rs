@@ -0,0 +1,12 @@
+mod difficulty;
+mod game;
+mod guess;
+pub mod scores;
+mod server;
+mod solver;
+
+pub use difficulty::{Difficulty, ParseDifficultyError};
+pub use game::{Game, Outcome, play, play_with_hints};
+pub use guess::Guess;
+pub use server::Server;
+pub use solver::{Simulation, Solver, simulate, solve};
@@ -1,63 +1,249 @@
-use rand::Rng;
-use std::cmp::Ordering;
+use guessing_game::scores::{self, Score};
+use guessing_game::{Difficulty, Game, Outcome};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::env;
 use std::io;
+use std::path::PathBuf;
+use std::process;
 
-// Create custom struct to avoid having to perform
-// validation on value (ensuring in [1, 100] range)
-// in every single function. If instead we receive
-// a Guess struct, we know for a fact the value is
-// valid with how the new() function is defined.
-struct Guess {
-    value: i32,
+const USAGE: &str = "\
+usage: guessing_game [--difficulty easy|normal|hard] [--seed N] [--name NAME]
+                     [--scores-file PATH] [--hints]
+       guessing_game --solve [--difficulty LEVEL] [--seed N]
+       guessing_game --simulate GAMES [--difficulty LEVEL] [--seed N]
+       guessing_game --scores [--scores-file PATH]
+       guessing_game --serve ADDRESS [--difficulty LEVEL] [--seed N]
+
+--hints tells you which numbers are still possible after each guess.
+--solve lets the computer play, and --simulate has it play GAMES games
+and reports how many attempts it needed.
+--serve hosts a multiplayer game on ADDRESS (e.g. 0.0.0.0:7878). Players
+connect with a line-based client such as `nc HOST 7878` and send
+GUESS <n>.
+
+High scores are kept in --scores-file, or $GUESSING_GAME_SCORES, or
+~/.guessing_game_scores. Wins in --seed games aren't saved, since the
+secret number is known in advance.";
+
+// How many entries --scores shows
+const LEADERBOARD_SIZE: usize = 10;
+
+struct Options {
+    difficulty: Difficulty,
+    // Fixes the secret number, for reproducible games
+    seed: Option<u64>,
+    player: String,
+    scores_file: PathBuf,
+    show_scores: bool,
+    hints: bool,
+    solve: bool,
+    simulate: Option<u32>,
+    serve: Option<String>,
 }
 
-impl Guess {
-    fn new(value: i32) -> Guess {
-        if value < 1 || value > 100 {
-            panic!("The number has to be between 1 and 100.");
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("[ERROR] {message}");
+            eprintln!("{USAGE}");
+            process::exit(2);
         }
+    };
 
-        Guess { value }
+    if options.show_scores {
+        match scores::load(&options.scores_file) {
+            Ok(table) => {
+                warn_about_corruption(&options, &table.corrupted_lines);
+                print!("{}", table.leaderboard(LEADERBOARD_SIZE));
+            }
+            Err(err) => {
+                eprintln!(
+                    "[ERROR] can't read {}: {err}",
+                    options.scores_file.display()
+                );
+                process::exit(1);
+            }
+        }
+        return;
     }
 
-    fn get_value(&self) -> i32 {
-        self.value
+    if let Some(address) = &options.serve {
+        let served =
+            guessing_game::Server::bind(address.as_str(), options.difficulty, options.seed)
+                .and_then(|server| {
+                    println!(
+                        "Serving {} games on {}",
+                        options.difficulty,
+                        server.local_addr()?
+                    );
+                    server.run()
+                });
+        if let Err(err) = served {
+            eprintln!("[ERROR] can't serve on {address}: {err}");
+            process::exit(1);
+        }
+        return;
     }
-}
-
-fn main() {
-    println!("Guess the number!");
 
-    let secret_number = rand::thread_rng().gen_range(1..=100);
+    let mut rng = match options.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
 
-    loop {
-        println!("Please input your guess.");
+    if let Some(games) = options.simulate {
+        let simulation = guessing_game::simulate(options.difficulty, games, &mut rng);
+        println!(
+            "Played {} games on {}: won {}, {:.2} attempts on average, {} at most.",
+            simulation.games,
+            options.difficulty,
+            simulation.wins,
+            simulation.average_attempts(),
+            simulation.most_attempts
+        );
+        return;
+    }
 
-        let mut guess = String::new();
+    let mut game = Game::new(options.difficulty, &mut rng);
 
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
+    // The computer's wins don't go on the leaderboard
+    if options.solve {
+        if let Err(err) = guessing_game::solve(&mut game, io::stdout()) {
+            eprintln!("[ERROR] {err}");
+            process::exit(1);
+        }
+        return;
+    }
 
-        let guess: i32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => {
-                println!("Please type a number!");
-                continue;
+    let stdin = io::stdin();
+    let played = if options.hints {
+        guessing_game::play_with_hints(&mut game, stdin.lock(), io::stdout())
+    } else {
+        guessing_game::play(&mut game, stdin.lock(), io::stdout())
+    };
+    match played {
+        // Anyone can replay a seed they've already won, so only
+        // games with a random secret go on the leaderboard
+        Ok(Outcome::Win { attempts, score }) if options.seed.is_none() => {
+            let score = Score::new(&options.player, score, attempts, options.difficulty.range());
+            // The game is over either way, so a score that can't be
+            // saved is only worth a warning.
+            if let Err(err) = scores::append(&options.scores_file, &score) {
+                eprintln!(
+                    "[WARN] couldn't save your score to {}: {err}",
+                    options.scores_file.display()
+                );
             }
-        };
+        }
+        Ok(_) => {}
+        Err(err) => {
+            eprintln!("[ERROR] {err}");
+            process::exit(1);
+        }
+    }
+}
 
-        let guess = Guess::new(guess);
+fn warn_about_corruption(options: &Options, lines: &[usize]) {
+    if !lines.is_empty() {
+        let lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        eprintln!(
+            "[WARN] skipped unreadable lines in {}: {}",
+            options.scores_file.display(),
+            lines.join(", ")
+        );
+    }
+}
 
-        println!("You guessed: {}", guess.get_value());
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        difficulty: Difficulty::default(),
+        seed: None,
+        player: default_player(),
+        scores_file: default_scores_file(),
+        show_scores: false,
+        hints: false,
+        solve: false,
+        simulate: None,
+        serve: None,
+    };
 
-        match guess.get_value().cmp(&secret_number) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
-                break;
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let switch = match flag.as_str() {
+            "--scores" => Some(&mut options.show_scores),
+            "--hints" => Some(&mut options.hints),
+            "--solve" => Some(&mut options.solve),
+            _ => None,
+        };
+        if let Some(switch) = switch {
+            *switch = true;
+            continue;
+        }
+
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for '{flag}'"))?;
+        match flag.as_str() {
+            "--difficulty" | "-d" => {
+                options.difficulty = value.parse().map_err(|err| format!("{err}"))?
+            }
+            "--seed" => {
+                options.seed = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("'{value}' is not a valid seed"))?,
+                )
+            }
+            "--name" => {
+                if value.trim().is_empty() {
+                    return Err(String::from("the player name can't be empty"));
+                }
+                options.player = value.clone()
             }
+            "--scores-file" => options.scores_file = PathBuf::from(value),
+            "--serve" => options.serve = Some(value.clone()),
+            "--simulate" => {
+                options.simulate = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("'{value}' is not a number of games"))?,
+                )
+            }
+            _ => return Err(format!("unknown option '{flag}'")),
         }
     }
+
+    let modes = [
+        options.show_scores,
+        options.solve,
+        options.simulate.is_some(),
+        options.serve.is_some(),
+    ];
+    if modes.iter().filter(|&&mode| mode).count() > 1 {
+        return Err(String::from(
+            "only one of --scores, --solve, --simulate and --serve can be used",
+        ));
+    }
+    Ok(options)
+}
+
+fn default_player() -> String {
+    env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .ok()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| String::from("anonymous"))
+}
+
+fn default_scores_file() -> PathBuf {
+    if let Some(path) = env::var_os("GUESSING_GAME_SCORES") {
+        return PathBuf::from(path);
+    }
+    match env::var_os("HOME") {
+        Some(home) => PathBuf::from(home).join(".guessing_game_scores"),
+        None => PathBuf::from("guessing_game_scores.txt"),
+    }
 }
@@ -0,0 +1,370 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+// One won game. On disk each score is a line of tab separated
+// fields:
+//
+//     player  score  attempts  low-high  yyyy-mm-dd
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Score {
+    pub player: String,
+    pub score: u32,
+    pub attempts: u32,
+    pub range: RangeInclusive<i32>,
+    pub date: Date,
+}
+
+impl Score {
+    // Tabs and newlines would break the file format, so they
+    // become spaces.
+    pub fn new(player: &str, score: u32, attempts: u32, range: RangeInclusive<i32>) -> Score {
+        let player = player
+            .trim()
+            .chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .collect();
+        Score {
+            player,
+            score,
+            attempts,
+            range,
+            date: Date::today(),
+        }
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}-{}\t{}",
+            self.player,
+            self.score,
+            self.attempts,
+            self.range.start(),
+            self.range.end(),
+            self.date
+        )
+    }
+}
+
+impl FromStr for Score {
+    type Err = ParseScoreError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let [player, score, attempts, range, date] = split_fields(line).ok_or(ParseScoreError)?;
+        // Either end can be negative, so the separator is the
+        // first '-' that isn't the start of the low end.
+        let separator = 1 + range
+            .get(1..)
+            .and_then(|rest| rest.find('-'))
+            .ok_or(ParseScoreError)?;
+        let (low, high) = (&range[..separator], &range[separator + 1..]);
+        let range = low.parse().map_err(|_| ParseScoreError)?
+            ..=high.parse().map_err(|_| ParseScoreError)?;
+
+        if player.is_empty() || range.is_empty() {
+            return Err(ParseScoreError);
+        }
+        Ok(Score {
+            player: player.to_string(),
+            score: score.parse().map_err(|_| ParseScoreError)?,
+            attempts: attempts.parse().map_err(|_| ParseScoreError)?,
+            range,
+            date: date.parse()?,
+        })
+    }
+}
+
+// Returned for a line or date that isn't in the score file
+// format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseScoreError;
+
+impl fmt::Display for ParseScoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not a valid high score entry")
+    }
+}
+
+impl Error for ParseScoreError {}
+
+fn split_fields(line: &str) -> Option<[&str; 5]> {
+    let mut fields = line.split('\t');
+    let parsed = [
+        fields.next()?,
+        fields.next()?,
+        fields.next()?,
+        fields.next()?,
+        fields.next()?,
+    ];
+    match fields.next() {
+        Some(_) => None,
+        None => Some(parsed),
+    }
+}
+
+// A calendar date in UTC, without pulling in a date crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub fn today() -> Date {
+        let seconds = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        Date::from_days_since_epoch((seconds / 86_400) as i64)
+    }
+
+    // Howard Hinnant's civil_from_days: count in 400 year eras,
+    // with years starting in March so leap days come last.
+    pub fn from_days_since_epoch(days: i64) -> Date {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Date {
+            year: year as i32,
+            month,
+            day,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = ParseScoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(3, '-');
+        let mut next = || parts.next().ok_or(ParseScoreError);
+        let (year, month, day) = (next()?, next()?, next()?);
+        let date = Date {
+            year: year.parse().map_err(|_| ParseScoreError)?,
+            month: month.parse().map_err(|_| ParseScoreError)?,
+            day: day.parse().map_err(|_| ParseScoreError)?,
+        };
+        if (1..=12).contains(&date.month) && (1..=31).contains(&date.day) {
+            Ok(date)
+        } else {
+            Err(ParseScoreError)
+        }
+    }
+}
+
+// Everything read back from a score file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScoreTable {
+    // Best first: highest score, then fewest attempts, then
+    // whoever got there first.
+    pub scores: Vec<Score>,
+    // Line numbers (from 1) that couldn't be read, e.g. after
+    // the file was edited by hand or cut short.
+    pub corrupted_lines: Vec<usize>,
+}
+
+impl ScoreTable {
+    // The top `count` scores as a printable table.
+    pub fn leaderboard(&self, count: usize) -> String {
+        if self.scores.is_empty() {
+            return String::from("No high scores yet.\n");
+        }
+
+        let mut table = format!(
+            "{:<4} {:<16} {:>5} {:>8}  {:<11} {}\n",
+            "Rank", "Player", "Score", "Attempts", "Range", "Date"
+        );
+        for (rank, score) in self.scores.iter().take(count).enumerate() {
+            let range = format!("{}-{}", score.range.start(), score.range.end());
+            table.push_str(&format!(
+                "{:<4} {:<16} {:>5} {:>8}  {:<11} {}\n",
+                rank + 1,
+                score.player,
+                score.score,
+                score.attempts,
+                range,
+                score.date
+            ));
+        }
+        table
+    }
+}
+
+// Reads every score in the file. A missing file is just an
+// empty table, and lines that don't parse are skipped and
+// reported in `corrupted_lines` rather than failing the load.
+pub fn load(path: &Path) -> io::Result<ScoreTable> {
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(ScoreTable::default()),
+        Err(err) => return Err(err),
+    };
+
+    let mut table = ScoreTable::default();
+    for (i, line) in String::from_utf8_lossy(&bytes).lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match line.parse() {
+            Ok(score) => table.scores.push(score),
+            Err(ParseScoreError) => table.corrupted_lines.push(i + 1),
+        }
+    }
+
+    // sort_by is stable, so equal scores stay in file order.
+    table.scores.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then(a.attempts.cmp(&b.attempts))
+            .then(a.date.cmp(&b.date))
+    });
+    Ok(table)
+}
+
+// Adds a score to the end of the file, creating it if needed.
+//
+// The file is locked while writing, so two games finishing at
+// the same time can't interleave their lines. If an earlier
+// write was cut short the file won't end in a newline, so one
+// is added first to keep the new score on its own line.
+pub fn append(path: &Path, score: &Score) -> io::Result<()> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .append(true)
+        .create(true)
+        .open(path)?;
+    file.lock()?;
+
+    let mut line = String::new();
+    if !ends_with_newline(&mut file)? {
+        line.push('\n');
+    }
+    line.push_str(&format!("{score}\n"));
+    file.write_all(line.as_bytes())?;
+    file.flush()
+    // Dropping the file releases the lock.
+}
+
+fn ends_with_newline(file: &mut File) -> io::Result<bool> {
+    if file.metadata()?.len() == 0 {
+        return Ok(true);
+    }
+    file.seek(SeekFrom::End(-1))?;
+    let mut last = [0];
+    file.read_exact(&mut last)?;
+    Ok(last[0] == b'\n')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(player: &str, score: u32, attempts: u32) -> Score {
+        Score {
+            player: player.to_string(),
+            score,
+            attempts,
+            range: 1..=100,
+            date: Date {
+                year: 2024,
+                month: 2,
+                day: 29,
+            },
+        }
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let original = score("Ferris the Crab", 120, 3);
+        let line = original.to_string();
+
+        assert_eq!(line, "Ferris the Crab\t120\t3\t1-100\t2024-02-29");
+        assert_eq!(line.parse(), Ok(original));
+
+        let negative = Score {
+            range: -50..=-10,
+            ..score("x", 1, 1)
+        };
+        assert_eq!(negative.to_string().parse(), Ok(negative));
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        for line in [
+            "",
+            "Ferris\t120\t3\t1-100",
+            "Ferris\t120\t3\t1-100\t2024-02-29\textra",
+            "Ferris\tlots\t3\t1-100\t2024-02-29",
+            "Ferris\t120\t3\t100-1\t2024-02-29",
+            "Ferris\t120\t3\t1-100\t2024-13-01",
+            "\t120\t3\t1-100\t2024-02-29",
+        ] {
+            assert_eq!(line.parse::<Score>(), Err(ParseScoreError), "{line:?}");
+        }
+    }
+
+    #[test]
+    fn names_cannot_break_the_format() {
+        let score = Score::new(" tab\there\nnewline ", 10, 1, 1..=10);
+
+        assert_eq!(score.player, "tab here newline");
+    }
+
+    #[test]
+    fn dates_from_days() {
+        let cases = [
+            (0, "1970-01-01"),
+            (-1, "1969-12-31"),
+            (11_016, "2000-02-29"),
+            (19_782, "2024-02-29"),
+            (20_745, "2026-10-19"),
+        ];
+        for (days, expected) in cases {
+            assert_eq!(Date::from_days_since_epoch(days).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn leaderboard_layout() {
+        let table = ScoreTable {
+            scores: vec![score("ada", 160, 1), score("bob", 20, 8)],
+            corrupted_lines: Vec::new(),
+        };
+
+        assert_eq!(
+            table.leaderboard(1),
+            "Rank Player           Score Attempts  Range       Date\n\
+             1    ada                160        1  1-100       2024-02-29\n"
+        );
+        assert_eq!(
+            ScoreTable::default().leaderboard(10),
+            "No high scores yet.\n"
+        );
+    }
+}
@@ -0,0 +1,388 @@
+use crate::difficulty::Difficulty;
+use crate::game::{Game, Outcome};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::mem;
+use std::net::{IpAddr, Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// A multiplayer server where everyone connected races to guess
+// the same secret number. Each player has their own attempts,
+// and the first to find the number wins the round; a new round
+// with a new number starts straight away for whoever connects
+// next. Attempts are counted per address, so hanging up and
+// coming back doesn't give a player a fresh set, and only one
+// connection per address can play a round at a time.
+//
+// The protocol is one line per message. The server greets each
+// player with
+//
+//     HELLO <low> <high> <attempts left>
+//
+// and then answers every `GUESS <n>` with one of
+//
+//     TOO_SMALL
+//     TOO_BIG
+//     WIN <attempts> <score>
+//     LOST              out of attempts, the round goes on
+//     LOST <secret>     out of attempts, and so is everyone else
+//     ERROR <message>   doesn't use up an attempt
+//
+// When somebody wins, every other player in the round is sent
+// `LOST <secret>`. When the last player left in a round runs
+// out of attempts, nobody can win it any more, so it ends the
+// same way. A player coming back to a round they have no
+// attempts left in gets `LOST` instead of HELLO, and a second
+// connection from an address that's already playing gets
+// `ERROR <message>`. After WIN or LOST, and after turning a
+// connection away, the connection is closed.
+pub struct Server {
+    listener: TcpListener,
+    lobby: Arc<Mutex<Lobby>>,
+}
+
+// How long sending a line to a player may take. A player who
+// stops reading only holds up whoever is sending to them, and
+// not for longer than this.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct Lobby {
+    difficulty: Difficulty,
+    rng: StdRng,
+    round: Arc<Mutex<Round>>,
+    next_player: usize,
+}
+
+// Nothing is sent while holding a round's lock: whoever holds
+// it works out what to send and to whom, and sends it after
+// letting go.
+struct Round {
+    secret: i32,
+    over: bool,
+    players: Vec<Arc<Seat>>,
+    // Attempts used by players who have left the round
+    spent: HashMap<IpAddr, u32>,
+}
+
+// A player's connection, as far as sending goes. Each line is
+// sent with the lock held, so a LOST sent by the winner's
+// thread can't land in the middle of the player's own reply.
+struct Seat {
+    id: usize,
+    address: IpAddr,
+    // None once the player's been sent their last line
+    writer: Mutex<Option<TcpStream>>,
+}
+
+// A round that has just ended, and the players who lost it.
+struct Ended {
+    secret: i32,
+    losers: Vec<Arc<Seat>>,
+}
+
+impl Round {
+    fn start(difficulty: Difficulty, rng: &mut StdRng) -> Arc<Mutex<Round>> {
+        Arc::new(Mutex::new(Round {
+            secret: rng.gen_range(difficulty.range()),
+            over: false,
+            players: Vec::new(),
+            spent: HashMap::new(),
+        }))
+    }
+
+    // Ends the round for everyone but `except` and starts the
+    // next one. Done while holding the round, so nobody can join
+    // it between it ending and the next one starting.
+    fn end(&mut self, lobby: &Mutex<Lobby>, except: usize) -> Ended {
+        self.over = true;
+        let mut lobby = lobby.lock().unwrap();
+        let lobby = &mut *lobby;
+        lobby.round = Round::start(lobby.difficulty, &mut lobby.rng);
+
+        Ended {
+            secret: self.secret,
+            losers: mem::take(&mut self.players)
+                .into_iter()
+                .filter(|seat| seat.id != except)
+                .collect(),
+        }
+    }
+
+    // Takes the player out of the round, keeping count of the
+    // attempts they used. Does nothing if they've already left.
+    fn leave(&mut self, seat: &Seat, game: &Game) {
+        let before = self.players.len();
+        self.players.retain(|other| other.id != seat.id);
+        if self.players.len() < before {
+            *self.spent.entry(seat.address).or_default() += game.attempts();
+        }
+    }
+}
+
+impl Ended {
+    fn tell_losers(self) {
+        for seat in self.losers {
+            // They may have gone already, which is fine
+            let _ = seat.close(&format!("LOST {}", self.secret));
+        }
+    }
+}
+
+impl Seat {
+    // Sends a line, unless the player's already been sent
+    // their last one.
+    fn send(&self, line: &str) -> io::Result<()> {
+        match &mut *self.writer.lock().unwrap() {
+            Some(stream) => send(stream, line),
+            None => Ok(()),
+        }
+    }
+
+    // Sends the player's last line and hangs up.
+    fn close(&self, line: &str) -> io::Result<()> {
+        let Some(mut stream) = self.writer.lock().unwrap().take() else {
+            return Ok(());
+        };
+        let result = send(&mut stream, line);
+        let _ = stream.shutdown(Shutdown::Both);
+        result
+    }
+}
+
+impl Server {
+    // Pass a seed to get the same secret numbers every time,
+    // round after round.
+    pub fn bind(
+        addr: impl ToSocketAddrs,
+        difficulty: Difficulty,
+        seed: Option<u64>,
+    ) -> io::Result<Server> {
+        let listener = TcpListener::bind(addr)?;
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let lobby = Lobby {
+            difficulty,
+            round: Round::start(difficulty, &mut rng),
+            rng,
+            next_player: 0,
+        };
+
+        Ok(Server {
+            listener,
+            lobby: Arc::new(Mutex::new(lobby)),
+        })
+    }
+
+    // Useful after binding to port 0 to find out which port
+    // was picked.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    // Accepts players forever, each on a thread of their own.
+    pub fn run(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    eprintln!("[WARN] failed to accept a connection: {err}");
+                    continue;
+                }
+            };
+
+            let lobby = Arc::clone(&self.lobby);
+            thread::spawn(move || {
+                if let Err(err) = serve_player(&lobby, stream) {
+                    eprintln!("[WARN] lost a player: {err}");
+                }
+            });
+        }
+        Ok(())
+    }
+}
+
+// One connected player's side of a round.
+struct Player {
+    round: Arc<Mutex<Round>>,
+    game: Game,
+    seat: Arc<Seat>,
+}
+
+fn serve_player(lobby: &Mutex<Lobby>, stream: TcpStream) -> io::Result<()> {
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let Some(mut player) = Player::join(lobby, stream.try_clone()?)? else {
+        return Ok(());
+    };
+    let result = player.play(lobby, BufReader::new(stream));
+
+    player
+        .round
+        .lock()
+        .unwrap()
+        .leave(&player.seat, &player.game);
+    result
+}
+
+impl Player {
+    // Adds the player to the current round and greets them, or
+    // turns them away if they've used up their attempts in it
+    // or are already playing it from another connection.
+    fn join(lobby: &Mutex<Lobby>, mut writer: TcpStream) -> io::Result<Option<Player>> {
+        let address = writer.peer_addr()?.ip();
+        loop {
+            let (id, difficulty, round) = {
+                let mut lobby = lobby.lock().unwrap();
+                lobby.next_player += 1;
+                (
+                    lobby.next_player,
+                    lobby.difficulty,
+                    Arc::clone(&lobby.round),
+                )
+            };
+
+            let mut current = round.lock().unwrap();
+            // The round was won after we picked it up, and the
+            // lobby has moved on to the next one
+            if current.over {
+                continue;
+            }
+
+            // The attempts of a seat still in the round are only
+            // added up when it leaves, so a second one would start
+            // from too few
+            if current.players.iter().any(|seat| seat.address == address) {
+                drop(current);
+                send(
+                    &mut writer,
+                    &format!("ERROR already playing from {address}"),
+                )?;
+                return Ok(None);
+            }
+
+            let used = current.spent.get(&address).copied().unwrap_or(0);
+            let game = Game::with_secret(difficulty, current.secret).with_attempts_used(used);
+            if game.is_over() {
+                drop(current);
+                send(&mut writer, "LOST")?;
+                return Ok(None);
+            }
+
+            let seat = Arc::new(Seat {
+                id,
+                address,
+                writer: Mutex::new(None),
+            });
+            // Hold the connection before anyone else can get to it
+            // through the round, so HELLO is the first thing sent
+            let mut seat_writer = seat.writer.lock().unwrap();
+            current.players.push(Arc::clone(&seat));
+            drop(current);
+
+            let range = difficulty.range();
+            let hello = format!(
+                "HELLO {} {} {}",
+                range.start(),
+                range.end(),
+                game.attempts_left()
+            );
+            let sent = send(&mut writer, &hello);
+            *seat_writer = Some(writer);
+            drop(seat_writer);
+
+            let player = Player { round, game, seat };
+            if let Err(err) = sent {
+                player
+                    .round
+                    .lock()
+                    .unwrap()
+                    .leave(&player.seat, &player.game);
+                return Err(err);
+            }
+            return Ok(Some(player));
+        }
+    }
+
+    // Answers guesses until the player's game or the round is
+    // over, or they hang up.
+    fn play(&mut self, lobby: &Mutex<Lobby>, reader: impl BufRead) -> io::Result<()> {
+        for line in reader.lines() {
+            let line = line?;
+            let Some(guess) = line.trim().strip_prefix("GUESS ") else {
+                self.seat.send("ERROR expected GUESS <number>")?;
+                continue;
+            };
+
+            let (outcome, ended) = {
+                let mut round = self.round.lock().unwrap();
+                // Somebody else won, and we've already been told
+                if round.over {
+                    return Ok(());
+                }
+
+                let outcome = self.game.guess(guess);
+                let ended = match outcome {
+                    Outcome::Win { .. } => Some(round.end(lobby, self.seat.id)),
+                    Outcome::Lost { .. } => {
+                        round.leave(&self.seat, &self.game);
+                        // Nobody's left who could still win
+                        round
+                            .players
+                            .is_empty()
+                            .then(|| round.end(lobby, self.seat.id))
+                    }
+                    _ => None,
+                };
+                (outcome, ended)
+            };
+
+            let last = match outcome {
+                Outcome::InvalidInput => {
+                    let error = format!("ERROR '{}' is not a number", guess.trim());
+                    self.seat.send(&error)?;
+                    continue;
+                }
+                Outcome::OutOfRange => {
+                    let range = self.game.difficulty().range();
+                    let error = format!(
+                        "ERROR the number has to be between {} and {}",
+                        range.start(),
+                        range.end()
+                    );
+                    self.seat.send(&error)?;
+                    continue;
+                }
+                Outcome::TooSmall => {
+                    self.seat.send("TOO_SMALL")?;
+                    continue;
+                }
+                Outcome::TooBig => {
+                    self.seat.send("TOO_BIG")?;
+                    continue;
+                }
+                Outcome::Lost { secret } if ended.is_some() => format!("LOST {secret}"),
+                Outcome::Lost { .. } => String::from("LOST"),
+                Outcome::Win { attempts, score } => format!("WIN {attempts} {score}"),
+            };
+
+            let result = self.seat.close(&last);
+            if let Some(ended) = ended {
+                ended.tell_losers();
+            }
+            return result;
+        }
+        Ok(())
+    }
+}
+
+// Writes a whole line at once. writeln! would write it in
+// pieces, and small writes on a socket can sit waiting for
+// the previous piece to be acknowledged.
+fn send(stream: &mut TcpStream, line: &str) -> io::Result<()> {
+    stream.write_all(format!("{line}\n").as_bytes())
+}
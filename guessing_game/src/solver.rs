@@ -0,0 +1,213 @@
+use crate::difficulty::Difficulty;
+use crate::game::{Game, Outcome};
+use crate::guess::Guess;
+use rand::Rng;
+use std::cmp::Ordering;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+
+// Plays the game by binary search: every guess is in the
+// middle of the numbers that are still possible, so each
+// answer rules out at least half of them.
+#[derive(Debug, Clone)]
+pub struct Solver {
+    range: RangeInclusive<i32>,
+    low: i32,
+    high: i32,
+}
+
+impl Solver {
+    pub fn new(range: RangeInclusive<i32>) -> Solver {
+        Solver {
+            low: *range.start(),
+            high: *range.end(),
+            range,
+        }
+    }
+
+    // The numbers the feedback so far hasn't ruled out. Empty
+    // if the feedback contradicted itself.
+    pub fn remaining(&self) -> RangeInclusive<i32> {
+        self.low..=self.high
+    }
+
+    // None once there's nothing left to guess.
+    pub fn next_guess(&self) -> Option<Guess> {
+        if self.low > self.high {
+            return None;
+        }
+        // Halve in i64 so ranges near the i32 limits can't overflow
+        let middle = (i64::from(self.low) + i64::from(self.high)).div_euclid(2);
+        Some(Guess::new(middle as i32, &self.range))
+    }
+
+    // Narrows the remaining numbers, given how `guess` compared
+    // to the secret number: Less means the guess was too small.
+    pub fn feedback(&mut self, guess: Guess, ordering: Ordering) {
+        let value = guess.get_value();
+        match ordering {
+            Ordering::Less => self.low = self.low.max(value.saturating_add(1)),
+            Ordering::Greater => self.high = self.high.min(value.saturating_sub(1)),
+            Ordering::Equal => {
+                self.low = self.low.max(value);
+                self.high = self.high.min(value);
+            }
+        }
+    }
+}
+
+// Lets the solver play `game` to the end, writing each of its
+// guesses and the answers to `output`. Returns the final Win or
+// Lost outcome.
+pub fn solve(game: &mut Game, mut output: impl Write) -> io::Result<Outcome> {
+    let range = game.difficulty().range();
+    let mut solver = Solver::new(range.clone());
+
+    writeln!(
+        output,
+        "The computer is guessing a number between {} and {}.",
+        range.start(),
+        range.end()
+    )?;
+    loop {
+        let guess = solver
+            .next_guess()
+            .expect("the secret number is never ruled out");
+        let outcome = game.guess_value(guess.get_value());
+        write!(output, "It guessed {}: ", guess.get_value())?;
+        match outcome {
+            Outcome::TooSmall => {
+                writeln!(output, "too small.")?;
+                solver.feedback(guess, Ordering::Less);
+            }
+            Outcome::TooBig => {
+                writeln!(output, "too big.")?;
+                solver.feedback(guess, Ordering::Greater);
+            }
+            Outcome::Win { attempts, score } => {
+                writeln!(output, "that's it!")?;
+                writeln!(
+                    output,
+                    "The computer wins in {attempts} attempts! Score: {score}"
+                )?;
+                return Ok(outcome);
+            }
+            Outcome::Lost { secret } => {
+                writeln!(output, "wrong.")?;
+                writeln!(output, "The computer loses! The number was {secret}.")?;
+                return Ok(outcome);
+            }
+            Outcome::InvalidInput | Outcome::OutOfRange => {
+                unreachable!("the solver only guesses numbers in range")
+            }
+        }
+    }
+}
+
+// Totals from letting the solver play many games.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Simulation {
+    pub games: u32,
+    pub wins: u32,
+    // Over every game, won or lost
+    pub total_attempts: u64,
+    pub most_attempts: u32,
+}
+
+impl Simulation {
+    pub fn average_attempts(&self) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        self.total_attempts as f64 / f64::from(self.games)
+    }
+}
+
+// Plays `games` games with secret numbers picked by `rng`.
+pub fn simulate(difficulty: Difficulty, games: u32, rng: &mut impl Rng) -> Simulation {
+    let mut simulation = Simulation::default();
+    for _ in 0..games {
+        let mut game = Game::new(difficulty, rng);
+        let outcome = solve(&mut game, io::sink()).expect("writing to a sink can't fail");
+
+        simulation.games += 1;
+        simulation.total_attempts += u64::from(game.attempts());
+        simulation.most_attempts = simulation.most_attempts.max(game.attempts());
+        if matches!(outcome, Outcome::Win { .. }) {
+            simulation.wins += 1;
+        }
+    }
+    simulation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn wins_every_game_in_time() {
+        for difficulty in [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard] {
+            for secret in difficulty.range() {
+                let mut game = Game::with_secret(difficulty, secret);
+                let outcome = solve(&mut game, io::sink()).unwrap();
+
+                assert!(
+                    matches!(outcome, Outcome::Win { .. }),
+                    "{difficulty} {secret}: {outcome:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn feedback_narrows_the_interval() {
+        let mut solver = Solver::new(1..=100);
+        let guess = solver.next_guess().unwrap();
+        assert_eq!(guess.get_value(), 50);
+
+        solver.feedback(guess, Ordering::Greater);
+        assert_eq!(solver.remaining(), 1..=49);
+        solver.feedback(Guess::new(20, &(1..=100)), Ordering::Less);
+        assert_eq!(solver.remaining(), 21..=49);
+
+        // A guess outside what's left tells us nothing new
+        solver.feedback(Guess::new(80, &(1..=100)), Ordering::Greater);
+        assert_eq!(solver.remaining(), 21..=49);
+        assert_eq!(solver.next_guess().unwrap().get_value(), 35);
+    }
+
+    #[test]
+    fn contradictions_leave_nothing_to_guess() {
+        let mut solver = Solver::new(1..=10);
+        solver.feedback(Guess::new(5, &(1..=10)), Ordering::Less);
+        solver.feedback(Guess::new(6, &(1..=10)), Ordering::Greater);
+
+        assert!(solver.remaining().is_empty());
+        assert_eq!(solver.next_guess(), None);
+    }
+
+    #[test]
+    fn handles_the_whole_i32_range() {
+        let mut solver = Solver::new(i32::MIN..=i32::MAX);
+        assert_eq!(solver.next_guess().unwrap().get_value(), -1);
+
+        solver.feedback(
+            Guess::new(i32::MAX - 1, &(i32::MIN..=i32::MAX)),
+            Ordering::Less,
+        );
+        assert_eq!(solver.next_guess().unwrap().get_value(), i32::MAX);
+    }
+
+    #[test]
+    fn simulation_is_repeatable() {
+        let first = simulate(Difficulty::Hard, 200, &mut StdRng::seed_from_u64(1));
+        let second = simulate(Difficulty::Hard, 200, &mut StdRng::seed_from_u64(1));
+
+        assert_eq!(first, second);
+        assert_eq!(first.wins, 200);
+        assert!(first.most_attempts <= Difficulty::Hard.max_attempts());
+        assert!((7.0..=10.0).contains(&first.average_attempts()));
+    }
+}
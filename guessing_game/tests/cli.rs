@@ -0,0 +1,228 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Output, Stdio};
+
+fn scores_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "guessing_game_cli_{}_{name}.txt",
+        std::process::id()
+    ));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+fn start(args: &[&str], scores_file: &Path) -> Child {
+    Command::new(env!("CARGO_BIN_EXE_guessing_game"))
+        .args(args)
+        .env("GUESSING_GAME_SCORES", scores_file)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to start guessing_game")
+}
+
+fn finish(mut child: Child, stdin: &str) -> Output {
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn guessing_game(args: &[&str], stdin: &str) -> Output {
+    finish(start(args, &scores_file("scratch")), stdin)
+}
+
+// Plays a normal game with a random secret by halving the range after
+// every answer, which always wins within its 8 attempts. Returns
+// whether the process succeeded and what it printed.
+fn play_by_halving(mut child: Child) -> (bool, String) {
+    let mut stdin = child.stdin.take().unwrap();
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+    let (mut low, mut high, mut guess) = (1, 100, 0);
+    let mut printed = String::new();
+
+    for line in stdout.lines() {
+        let line = line.unwrap();
+        match line.as_str() {
+            "Please input your guess." => {
+                guess = (low + high) / 2;
+                writeln!(stdin, "{guess}").unwrap();
+            }
+            "Too small!" => low = guess + 1,
+            "Too big!" => high = guess - 1,
+            _ => {}
+        }
+        printed.push_str(&line);
+        printed.push('\n');
+    }
+    drop(stdin);
+    (child.wait().unwrap().success(), printed)
+}
+
+#[test]
+fn seeded_game_from_the_command_line() {
+    // Seed 7 on hard hides 417.
+    let output = guessing_game(&["--seed", "7", "-d", "hard"], "500\n250\n417\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(
+        stdout
+            .starts_with("Guess the number!\nIt's between 1 and 1000, and you have 10 attempts.\n")
+    );
+    assert!(stdout.ends_with("You guessed: 417\nYou win in 3 attempts! Score: 400\n"));
+}
+
+#[test]
+fn input_ending_early_is_an_error() {
+    let output = guessing_game(&["--seed", "7"], "1\n");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "[ERROR] input ended before the game did\n"
+    );
+}
+
+#[test]
+fn bad_arguments_print_usage() {
+    for args in [
+        &["--seed", "x"][..],
+        &["--difficulty"],
+        &["--level", "easy"],
+        &["--simulate", "lots"],
+        &["--solve", "--scores"],
+    ] {
+        let output = guessing_game(args, "");
+
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        assert!(
+            String::from_utf8(output.stderr)
+                .unwrap()
+                .contains("usage: guessing_game")
+        );
+    }
+}
+
+#[test]
+fn wins_are_saved_and_shown_with_scores() {
+    let path = scores_file("leaderboard");
+    let output = finish(start(&["--scores"], &path), "");
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "No high scores yet.\n"
+    );
+
+    // Several games finishing at once all get their line in.
+    let games: Vec<_> = (0..4)
+        .map(|i| {
+            let name = format!("player{i}");
+            let game = start(&["--name", &name], &path);
+            std::thread::spawn(move || play_by_halving(game))
+        })
+        .collect();
+    for game in games {
+        let (succeeded, stdout) = game.join().unwrap();
+        assert!(succeeded);
+        assert!(stdout.contains("You win in "), "{stdout}");
+    }
+
+    let output = finish(start(&["--scores"], &path), "");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].starts_with("Rank Player"));
+    for i in 0..4 {
+        let name = format!(" player{i} ");
+        assert!(
+            lines[1..].iter().any(|line| line.contains(&name)),
+            "{stdout}"
+        );
+    }
+    assert!(lines[1..].iter().all(|line| line.contains("  1-100")));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn seeded_wins_are_not_saved() {
+    let path = scores_file("seeded");
+    let output = finish(start(&["--seed", "7", "--name", "ace"], &path), "42\n");
+    assert!(
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .ends_with("You win in 1 attempts! Score: 160\n")
+    );
+    assert!(output.stderr.is_empty());
+
+    let output = finish(start(&["--scores"], &path), "");
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "No high scores yet.\n"
+    );
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn corrupted_score_file_is_a_warning() {
+    let path = scores_file("corrupted");
+    fs::write(&path, "garbage\nada\t100\t2\t1-100\t2026-10-19\n").unwrap();
+
+    let output = finish(start(&["--scores"], &path), "");
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout).unwrap().contains("ada"));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        format!("[WARN] skipped unreadable lines in {}: 1\n", path.display())
+    );
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn hints_narrow_down_the_number() {
+    let output = guessing_game(&["--seed", "7", "--hints"], "50\n25\n42\n");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Too big!\nHint: it's between 1 and 49.\n"));
+    assert!(stdout.contains("Too small!\nHint: it's between 26 and 49.\n"));
+}
+
+#[test]
+fn computer_solves_a_seeded_game() {
+    let output = guessing_game(&["--seed", "7", "--solve"], "");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "The computer is guessing a number between 1 and 100.\n\
+         It guessed 50: too big.\n\
+         It guessed 25: too small.\n\
+         It guessed 37: too small.\n\
+         It guessed 43: too big.\n\
+         It guessed 40: too small.\n\
+         It guessed 41: too small.\n\
+         It guessed 42: that's it!\n\
+         The computer wins in 7 attempts! Score: 40\n"
+    );
+}
+
+#[test]
+fn simulation_reports_average_attempts() {
+    let output = guessing_game(&["--simulate", "500", "--seed", "1", "-d", "easy"], "");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.starts_with("Played 500 games on easy: won 500, "),
+        "{stdout}"
+    );
+    assert!(
+        stdout.ends_with(" attempts on average, 5 at most.\n"),
+        "{stdout}"
+    );
+}
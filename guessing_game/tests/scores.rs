@@ -0,0 +1,115 @@
+use guessing_game::scores::{self, Date, Score};
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+
+// A fresh path per test, so tests running in parallel don't share files.
+fn scores_file(name: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("guessing_game_{}_{name}.txt", std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+fn score(player: &str, score: u32, attempts: u32) -> Score {
+    Score {
+        player: player.to_string(),
+        score,
+        attempts,
+        range: 1..=100,
+        date: Date {
+            year: 2026,
+            month: 10,
+            day: 19,
+        },
+    }
+}
+
+#[test]
+fn missing_file_is_empty() {
+    let table = scores::load(&scores_file("missing")).unwrap();
+
+    assert!(table.scores.is_empty());
+    assert!(table.corrupted_lines.is_empty());
+}
+
+#[test]
+fn scores_come_back_best_first() {
+    let path = scores_file("ordering");
+    for entry in [
+        score("slow", 20, 8),
+        score("fast", 160, 1),
+        score("tied-second", 120, 3),
+        score("tied-first", 120, 3),
+    ] {
+        scores::append(&path, &entry).unwrap();
+    }
+    let table = scores::load(&path).unwrap();
+    let players: Vec<_> = table.scores.iter().map(|s| s.player.as_str()).collect();
+
+    assert_eq!(players, ["fast", "tied-second", "tied-first", "slow"]);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn corrupted_lines_are_skipped_and_reported() {
+    let path = scores_file("corrupted");
+    fs::write(
+        &path,
+        format!(
+            "{}\nthis is not a score\n\n\u{0}\u{ff}garbage\n{}\nhalf\t12",
+            score("ada", 100, 2),
+            score("bob", 50, 5),
+        ),
+    )
+    .unwrap();
+
+    let table = scores::load(&path).unwrap();
+    assert_eq!(table.scores.len(), 2);
+    assert_eq!(table.corrupted_lines, [2, 4, 6]);
+
+    // The cut-off last line doesn't swallow the next score.
+    scores::append(&path, &score("cy", 70, 4)).unwrap();
+    let table = scores::load(&path).unwrap();
+    assert_eq!(table.scores.len(), 3);
+    assert_eq!(table.corrupted_lines, [2, 4, 6]);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn invalid_utf8_is_just_another_corrupted_line() {
+    let path = scores_file("binary");
+    let mut bytes = format!("{}\n", score("ada", 100, 2)).into_bytes();
+    bytes.extend_from_slice(&[0xc3, 0x28, b'\n']);
+    fs::write(&path, bytes).unwrap();
+
+    let table = scores::load(&path).unwrap();
+    assert_eq!(table.scores.len(), 1);
+    assert_eq!(table.corrupted_lines, [2]);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn concurrent_appends_keep_every_line_whole() {
+    let path = scores_file("concurrent");
+    let writers = 8;
+    let per_writer = 50;
+
+    thread::scope(|scope| {
+        for writer in 0..writers {
+            let path = &path;
+            scope.spawn(move || {
+                // A long name makes torn writes more likely to show up.
+                let name = format!("player-{writer}-{}", "x".repeat(200));
+                for i in 0..per_writer {
+                    scores::append(path, &score(&name, i, 1)).unwrap();
+                }
+            });
+        }
+    });
+
+    let table = scores::load(&path).unwrap();
+    assert!(table.corrupted_lines.is_empty());
+    assert_eq!(table.scores.len(), (writers * per_writer) as usize);
+    fs::remove_file(path).unwrap();
+}
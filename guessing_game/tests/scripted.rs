@@ -0,0 +1,94 @@
+use guessing_game::{Difficulty, Game, Outcome, play};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+// With this seed the secret number is 9 on easy, 42 on normal
+// and 417 on hard.
+const SEED: u64 = 7;
+
+fn seeded_game(difficulty: Difficulty) -> Game {
+    Game::new(difficulty, &mut StdRng::seed_from_u64(SEED))
+}
+
+fn play_script(difficulty: Difficulty, script: &str) -> (Outcome, String) {
+    let mut game = seeded_game(difficulty);
+    let mut output = Vec::new();
+    let outcome = play(&mut game, script.as_bytes(), &mut output).unwrap();
+    (outcome, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn same_seed_same_secret() {
+    for difficulty in [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard] {
+        let mut first = seeded_game(difficulty);
+        let mut second = seeded_game(difficulty);
+
+        for value in difficulty.range() {
+            let outcome = first.guess_value(value);
+            assert_eq!(second.guess_value(value), outcome);
+            if first.is_over() {
+                break;
+            }
+        }
+    }
+}
+
+#[test]
+fn binary_search_wins_on_hard() {
+    let mut game = seeded_game(Difficulty::Hard);
+    let (mut low, mut high) = (1, 1000);
+
+    let attempts = loop {
+        let guess = (low + high) / 2;
+        match game.guess(&guess.to_string()) {
+            Outcome::TooSmall => low = guess + 1,
+            Outcome::TooBig => high = guess - 1,
+            Outcome::Win { attempts, .. } => break attempts,
+            other => panic!("unexpected {other:?} for {guess}"),
+        }
+    };
+
+    assert!(attempts <= 10);
+    assert!(game.is_over());
+}
+
+#[test]
+fn full_winning_game() {
+    let (outcome, output) = play_script(Difficulty::Normal, "50\nabc\n0\n25\n42\n");
+
+    assert_eq!(
+        outcome,
+        Outcome::Win {
+            attempts: 3,
+            score: 120
+        }
+    );
+    assert_eq!(
+        output,
+        "Guess the number!\n\
+         It's between 1 and 100, and you have 8 attempts.\n\
+         Please input your guess.\n\
+         You guessed: 50\n\
+         Too big!\n\
+         Please input your guess.\n\
+         Please type a number!\n\
+         Please input your guess.\n\
+         The number has to be between 1 and 100.\n\
+         Please input your guess.\n\
+         You guessed: 25\n\
+         Too small!\n\
+         Please input your guess.\n\
+         You guessed: 42\n\
+         You win in 3 attempts! Score: 120\n"
+    );
+}
+
+#[test]
+fn full_losing_game() {
+    let (outcome, output) = play_script(Difficulty::Easy, "1\n2\n3\n20\n19\n18\nunused\n");
+
+    assert_eq!(outcome, Outcome::Lost { secret: 9 });
+    assert!(output.ends_with("You guessed: 18\nYou lose! The number was 9.\n"));
+    assert_eq!(output.matches("Too small!").count(), 3);
+    assert_eq!(output.matches("Too big!").count(), 2);
+}
@@ -0,0 +1,226 @@
+use guessing_game::{Difficulty, Server};
+use socket2::{Domain, Socket, Type};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpStream};
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::Duration;
+
+// With this seed the first secret number is 9 on easy and 42
+// on normal.
+const SEED: u64 = 7;
+
+fn start_server(difficulty: Difficulty) -> SocketAddr {
+    let server = Server::bind("127.0.0.1:0", difficulty, Some(SEED)).unwrap();
+    let addr = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+    addr
+}
+
+struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    fn connect(addr: SocketAddr) -> Client {
+        Client::connect_from(addr, 1)
+    }
+
+    // Connects from 127.0.0.<host>. The server counts attempts
+    // per address, so players who should be told apart need
+    // hosts of their own.
+    fn connect_from(addr: SocketAddr, host: u8) -> Client {
+        let socket = Socket::new(Domain::IPV4, Type::STREAM, None).unwrap();
+        let local = SocketAddr::from((Ipv4Addr::new(127, 0, 0, host), 0));
+        socket.bind(&local.into()).unwrap();
+        socket.connect(&addr.into()).unwrap();
+        let stream = TcpStream::from(socket);
+        // A hung test is worse than a failed one
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+        Client {
+            writer: stream.try_clone().unwrap(),
+            reader: BufReader::new(stream),
+        }
+    }
+
+    // The next line from the server, or "" once it hangs up.
+    fn recv(&mut self) -> String {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        line.trim_end().to_string()
+    }
+
+    fn send(&mut self, line: &str) -> String {
+        // The server may already have hung up on us, in which
+        // case its last words are still waiting to be read
+        let _ = self.writer.write_all(format!("{line}\n").as_bytes());
+        self.recv()
+    }
+
+    fn guess(&mut self, value: i32) -> String {
+        self.send(&format!("GUESS {value}"))
+    }
+
+    // Binary search until the game ends, returning the last
+    // reply.
+    fn solve(&mut self, mut low: i32, mut high: i32) -> String {
+        loop {
+            let guess = (low + high) / 2;
+            match self.guess(guess).as_str() {
+                "TOO_SMALL" => low = guess + 1,
+                "TOO_BIG" => high = guess - 1,
+                reply => return reply.to_string(),
+            }
+        }
+    }
+}
+
+#[test]
+fn answers_every_kind_of_guess() {
+    let addr = start_server(Difficulty::Normal);
+    let mut client = Client::connect(addr);
+
+    assert_eq!(client.recv(), "HELLO 1 100 8");
+    assert_eq!(client.guess(50), "TOO_BIG");
+    assert_eq!(client.send("hello"), "ERROR expected GUESS <number>");
+    assert_eq!(client.send("GUESS abc"), "ERROR 'abc' is not a number");
+    assert_eq!(
+        client.guess(101),
+        "ERROR the number has to be between 1 and 100"
+    );
+    assert_eq!(client.guess(25), "TOO_SMALL");
+    assert_eq!(client.guess(42), "WIN 3 120");
+    assert_eq!(client.recv(), "");
+}
+
+#[test]
+fn first_to_guess_wins_the_round() {
+    let addr = start_server(Difficulty::Normal);
+    let mut slow = Client::connect(addr);
+    let mut fast = Client::connect_from(addr, 2);
+    assert_eq!(slow.recv(), "HELLO 1 100 8");
+    assert_eq!(fast.recv(), "HELLO 1 100 8");
+
+    assert_eq!(slow.guess(50), "TOO_BIG");
+    assert_eq!(fast.guess(42), "WIN 1 160");
+    assert_eq!(slow.recv(), "LOST 42");
+    assert_eq!(slow.recv(), "");
+
+    // Whoever connects next gets a new round, with a new number
+    let mut next = Client::connect(addr);
+    assert_eq!(next.recv(), "HELLO 1 100 8");
+    let reply = next.solve(1, 100);
+    assert!(reply.starts_with("WIN "), "{reply}");
+}
+
+#[test]
+fn running_out_of_attempts_leaves_the_round_going() {
+    let addr = start_server(Difficulty::Easy);
+    let mut unlucky = Client::connect(addr);
+    let mut patient = Client::connect_from(addr, 2);
+    assert_eq!(unlucky.recv(), "HELLO 1 20 6");
+    assert_eq!(patient.recv(), "HELLO 1 20 6");
+
+    for guess in 1..6 {
+        assert_eq!(unlucky.guess(guess), "TOO_SMALL");
+    }
+    assert_eq!(unlucky.guess(20), "LOST");
+    assert_eq!(unlucky.recv(), "");
+
+    assert_eq!(patient.guess(9), "WIN 1 60");
+}
+
+#[test]
+fn reconnecting_doesnt_give_more_attempts() {
+    let addr = start_server(Difficulty::Easy);
+    let mut unlucky = Client::connect(addr);
+    let mut patient = Client::connect_from(addr, 2);
+    assert_eq!(unlucky.recv(), "HELLO 1 20 6");
+    assert_eq!(patient.recv(), "HELLO 1 20 6");
+
+    for guess in 1..7 {
+        unlucky.guess(guess);
+    }
+    let mut unlucky = Client::connect(addr);
+    assert_eq!(unlucky.recv(), "LOST");
+    assert_eq!(unlucky.recv(), "");
+
+    assert_eq!(patient.guess(9), "WIN 1 60");
+
+    // A new round is a fresh start
+    let mut unlucky = Client::connect(addr);
+    assert_eq!(unlucky.recv(), "HELLO 1 20 6");
+}
+
+#[test]
+fn one_connection_per_address_in_a_round() {
+    let addr = start_server(Difficulty::Easy);
+    let mut first = Client::connect(addr);
+    assert_eq!(first.recv(), "HELLO 1 20 6");
+    assert_eq!(first.guess(1), "TOO_SMALL");
+
+    let mut second = Client::connect(addr);
+    assert_eq!(second.recv(), "ERROR already playing from 127.0.0.1");
+    assert_eq!(second.recv(), "");
+
+    // Turning the second one away cost the first nothing
+    for guess in 2..6 {
+        assert_eq!(first.guess(guess), "TOO_SMALL");
+    }
+    assert_eq!(first.guess(9), "WIN 6 10");
+}
+
+#[test]
+fn round_ends_when_everyone_runs_out() {
+    let addr = start_server(Difficulty::Easy);
+    let mut first = Client::connect(addr);
+    let mut second = Client::connect_from(addr, 2);
+    assert_eq!(first.recv(), "HELLO 1 20 6");
+    assert_eq!(second.recv(), "HELLO 1 20 6");
+
+    for guess in 1..6 {
+        assert_eq!(first.guess(guess), "TOO_SMALL");
+    }
+    assert_eq!(first.guess(20), "LOST");
+    for guess in 1..6 {
+        assert_eq!(second.guess(guess), "TOO_SMALL");
+    }
+    assert_eq!(second.guess(20), "LOST 9");
+    assert_eq!(second.recv(), "");
+
+    // Whoever comes next plays a new round with all their
+    // attempts, however many the last one took
+    let mut next = Client::connect(addr);
+    assert_eq!(next.recv(), "HELLO 1 20 6");
+}
+
+#[test]
+fn exactly_one_winner_among_many_players() {
+    let addr = start_server(Difficulty::Hard);
+    let players: u8 = 8;
+    // Everyone joins before anyone guesses, so they're all in
+    // the same round
+    let joined = Arc::new(Barrier::new(usize::from(players)));
+
+    let handles: Vec<_> = (0..players)
+        .map(|i| {
+            let joined = Arc::clone(&joined);
+            thread::spawn(move || {
+                let mut client = Client::connect_from(addr, 10 + i);
+                assert_eq!(client.recv(), "HELLO 1 1000 10");
+                joined.wait();
+                client.solve(1, 1000)
+            })
+        })
+        .collect();
+    let replies: Vec<String> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+    let winners = replies.iter().filter(|r| r.starts_with("WIN ")).count();
+    assert_eq!(winners, 1, "{replies:?}");
+    let losers: Vec<_> = replies.iter().filter(|r| r.starts_with("LOST ")).collect();
+    assert_eq!(losers.len(), usize::from(players) - 1, "{replies:?}");
+    assert!(losers.iter().all(|&r| r == losers[0]));
+}
@@ -0,0 +1,42 @@
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use rectangles::Rectangle;
+use rectangles::packing::{Algorithm, Packer};
+use std::hint::black_box;
+
+// Sprite-like sizes from a fixed xorshift sequence, so every run packs the
+// same items without pulling in a random number crate.
+fn sprites(count: usize) -> Vec<Rectangle> {
+    let mut state = 0x2545_f491_u32;
+    let mut next = move |max: u32| {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        1 + state % max
+    };
+    (0..count)
+        .map(|_| Rectangle::new(next(64), next(64)))
+        .collect()
+}
+
+fn bench_algorithms(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pack");
+    for count in [100, 1_000] {
+        let items = sprites(count);
+        for algorithm in [Algorithm::Shelf, Algorithm::MaxRects] {
+            for rotation in [false, true] {
+                let packer = Packer::new(512, 512)
+                    .with_algorithm(algorithm)
+                    .with_rotation(rotation);
+                let name = format!("{algorithm:?}{}", if rotation { "+rotation" } else { "" });
+
+                group.bench_with_input(BenchmarkId::new(name, count), &items, |b, items| {
+                    b.iter(|| packer.pack(black_box(items)))
+                });
+            }
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_algorithms);
+criterion_main!(benches);
@@ -0,0 +1,360 @@
+//! Axis-aligned rectangles on an integer (pixel) grid.
+//!
+//! A rectangle covers the half-open area `[x, x + width) × [y, y + height)`,
+//! so two rectangles that only share an edge don't overlap, and a point on
+//! the right or bottom edge isn't inside.
+
+use std::fmt;
+
+/// A position on the grid. `y` grows downwards, as it does on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// A rectangle with its top-left corner at `origin`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rectangle {
+    pub origin: Point,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rectangle {
+    /// A rectangle with its top-left corner at `(0, 0)`.
+    pub const fn new(width: u32, height: u32) -> Rectangle {
+        Rectangle::at(0, 0, width, height)
+    }
+
+    pub const fn at(x: i32, y: i32, width: u32, height: u32) -> Rectangle {
+        Rectangle {
+            origin: Point::new(x, y),
+            width,
+            height,
+        }
+    }
+
+    pub const fn square(size: u32) -> Rectangle {
+        Rectangle::new(size, size)
+    }
+
+    /// The same rectangle moved to `origin`.
+    pub const fn with_origin(self, origin: Point) -> Rectangle {
+        Rectangle { origin, ..self }
+    }
+
+    pub const fn area(&self) -> u64 {
+        self.width as u64 * self.height as u64
+    }
+
+    pub const fn perimeter(&self) -> u64 {
+        2 * (self.width as u64 + self.height as u64)
+    }
+
+    /// True if the rectangle covers no points at all.
+    pub const fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    pub const fn left(&self) -> i64 {
+        self.origin.x as i64
+    }
+
+    pub const fn top(&self) -> i64 {
+        self.origin.y as i64
+    }
+
+    // The right and bottom edges can be past i32::MAX, so they're i64.
+    pub const fn right(&self) -> i64 {
+        self.left() + self.width as i64
+    }
+
+    pub const fn bottom(&self) -> i64 {
+        self.top() + self.height as i64
+    }
+
+    /// The same rectangle turned 90°, keeping its origin.
+    pub const fn rotated(&self) -> Rectangle {
+        Rectangle {
+            origin: self.origin,
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    /// True if `other` fits strictly inside this rectangle, i.e. it's
+    /// narrower and shorter. Positions are ignored.
+    pub fn can_hold(&self, other: &Rectangle) -> bool {
+        self.width > other.width && self.height > other.height
+    }
+
+    /// Like [`can_hold`](Rectangle::can_hold), but edges may be the same
+    /// length, so a rectangle can fit itself.
+    pub fn can_fit(&self, other: &Rectangle) -> bool {
+        self.width >= other.width && self.height >= other.height
+    }
+
+    /// Like [`can_hold`](Rectangle::can_hold), but `other` may also be
+    /// turned 90° to fit.
+    pub fn can_hold_rotated(&self, other: &Rectangle) -> bool {
+        self.can_hold(other) || self.can_hold(&other.rotated())
+    }
+
+    /// Like [`can_fit`](Rectangle::can_fit), but `other` may also be
+    /// turned 90° to fit.
+    pub fn can_fit_rotated(&self, other: &Rectangle) -> bool {
+        self.can_fit(other) || self.can_fit(&other.rotated())
+    }
+
+    pub fn contains_point(&self, point: Point) -> bool {
+        let (x, y) = (point.x as i64, point.y as i64);
+        self.left() <= x && x < self.right() && self.top() <= y && y < self.bottom()
+    }
+
+    /// True if every point of `other` is inside this rectangle. Empty
+    /// rectangles are contained anywhere within the bounds.
+    pub fn contains(&self, other: &Rectangle) -> bool {
+        self.left() <= other.left()
+            && other.right() <= self.right()
+            && self.top() <= other.top()
+            && other.bottom() <= self.bottom()
+    }
+
+    /// True if the rectangles share at least one point. Touching edges
+    /// don't count.
+    pub fn intersects(&self, other: &Rectangle) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The area both rectangles cover, or `None` if they don't overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rectangles::Rectangle;
+    ///
+    /// let a = Rectangle::at(0, 0, 10, 10);
+    /// let b = Rectangle::at(5, 5, 10, 10);
+    /// assert_eq!(a.intersection(&b), Some(Rectangle::at(5, 5, 5, 5)));
+    ///
+    /// let touching = Rectangle::at(10, 0, 10, 10);
+    /// assert_eq!(a.intersection(&touching), None);
+    /// ```
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        let left = self.left().max(other.left());
+        let top = self.top().max(other.top());
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+        if left >= right || top >= bottom {
+            return None;
+        }
+
+        // The overlap is inside both rectangles, so it fits their types.
+        Some(Rectangle::at(
+            left as i32,
+            top as i32,
+            (right - left) as u32,
+            (bottom - top) as u32,
+        ))
+    }
+
+    /// The smallest rectangle that contains both rectangles.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result is wider or taller than `u32::MAX`, which can
+    /// only happen for rectangles at opposite ends of the grid. Use
+    /// [`checked_union`](Rectangle::checked_union) where that can happen.
+    pub fn union(&self, other: &Rectangle) -> Rectangle {
+        self.checked_union(other)
+            .expect("union is too wide or too tall")
+    }
+
+    /// The smallest rectangle that contains both rectangles, or `None` if
+    /// it would be wider or taller than `u32::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rectangles::Rectangle;
+    ///
+    /// let a = Rectangle::at(0, 0, 2, 2);
+    /// let b = Rectangle::at(5, -3, 1, 1);
+    /// assert_eq!(a.checked_union(&b), Some(Rectangle::at(0, -3, 6, 5)));
+    ///
+    /// let far_left = Rectangle::at(i32::MIN, 0, 1, 1);
+    /// let far_right = Rectangle::at(i32::MAX, 0, 1, 1);
+    /// assert_eq!(far_left.checked_union(&far_right), None);
+    /// ```
+    pub fn checked_union(&self, other: &Rectangle) -> Option<Rectangle> {
+        let left = self.left().min(other.left());
+        let top = self.top().min(other.top());
+        let right = self.right().max(other.right());
+        let bottom = self.bottom().max(other.bottom());
+
+        Some(Rectangle::at(
+            left as i32,
+            top as i32,
+            u32::try_from(right - left).ok()?,
+            u32::try_from(bottom - top).ok()?,
+        ))
+    }
+
+    /// The same rectangle moved by `dx` and `dy`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new origin is off the grid, i.e. past `i32::MIN` or
+    /// `i32::MAX`, in release builds too.
+    pub fn translate(&self, dx: i32, dy: i32) -> Rectangle {
+        let x = self.origin.x.checked_add(dx).expect("moved off the grid");
+        let y = self.origin.y.checked_add(dy).expect("moved off the grid");
+        self.with_origin(Point::new(x, y))
+    }
+
+    /// The same rectangle with both sides multiplied by `factor`. The
+    /// origin stays put.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a side ends up longer than `u32::MAX`, in release builds
+    /// too.
+    pub fn scale(&self, factor: u32) -> Rectangle {
+        self.scale_xy(factor, factor)
+    }
+
+    /// Like [`scale`](Rectangle::scale), with separate factors for the
+    /// width and the height.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`scale`](Rectangle::scale).
+    pub fn scale_xy(&self, x_factor: u32, y_factor: u32) -> Rectangle {
+        Rectangle {
+            origin: self.origin,
+            width: self.width.checked_mul(x_factor).expect("scaled too wide"),
+            height: self.height.checked_mul(y_factor).expect("scaled too tall"),
+        }
+    }
+}
+
+impl fmt::Display for Rectangle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{} at {}", self.width, self.height, self.origin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn area_and_perimeter() {
+        let rect = Rectangle::new(30, 50);
+
+        assert_eq!(rect.area(), 1500);
+        assert_eq!(rect.perimeter(), 160);
+        assert_eq!(
+            Rectangle::square(u32::MAX).area(),
+            u32::MAX as u64 * u32::MAX as u64
+        );
+    }
+
+    #[test]
+    fn can_hold_variants() {
+        let big = Rectangle::new(8, 7);
+        let small = Rectangle::new(5, 1);
+        let tall = Rectangle::new(1, 8);
+
+        assert!(big.can_hold(&small));
+        assert!(!small.can_hold(&big));
+        assert!(!big.can_hold(&big));
+        assert!(big.can_fit(&big));
+        assert!(!big.can_hold(&tall));
+        assert!(!big.can_hold_rotated(&tall));
+        assert!(big.can_fit_rotated(&tall));
+        assert!(!big.can_fit(&tall));
+    }
+
+    #[test]
+    fn edges_are_half_open() {
+        let rect = Rectangle::at(-2, -2, 4, 4);
+
+        assert!(rect.contains_point(Point::new(-2, -2)));
+        assert!(rect.contains_point(Point::new(1, 1)));
+        assert!(!rect.contains_point(Point::new(2, 0)));
+        assert!(!rect.contains_point(Point::new(0, 2)));
+        assert!(!rect.intersects(&Rectangle::at(2, -2, 4, 4)));
+        assert!(rect.intersects(&Rectangle::at(1, 1, 4, 4)));
+    }
+
+    #[test]
+    fn empty_rectangles_never_intersect() {
+        let empty = Rectangle::at(1, 1, 0, 5);
+
+        assert!(empty.is_empty());
+        assert!(!empty.contains_point(Point::new(1, 1)));
+        assert!(!empty.intersects(&Rectangle::new(10, 10)));
+        assert!(Rectangle::new(10, 10).contains(&empty));
+    }
+
+    #[test]
+    fn union_covers_both() {
+        let a = Rectangle::at(0, 0, 2, 2);
+        let b = Rectangle::at(5, -3, 1, 1);
+
+        assert_eq!(a.union(&b), Rectangle::at(0, -3, 6, 5));
+    }
+
+    #[test]
+    #[should_panic(expected = "union is too wide or too tall")]
+    fn union_across_the_whole_grid_panics() {
+        let top = Rectangle::at(0, i32::MIN, 1, 1);
+        let bottom = Rectangle::at(0, i32::MAX, 1, 1);
+
+        top.union(&bottom);
+    }
+
+    #[test]
+    fn intersection_at_the_edge_of_the_grid() {
+        let a = Rectangle::at(i32::MAX - 1, 0, u32::MAX, 1);
+        let b = Rectangle::at(i32::MAX, 0, 10, 1);
+
+        assert_eq!(a.intersection(&b), Some(b));
+    }
+
+    #[test]
+    fn translate_and_scale() {
+        let rect = Rectangle::at(1, 2, 3, 4);
+
+        assert_eq!(rect.translate(-1, 3), Rectangle::at(0, 5, 3, 4));
+        assert_eq!(rect.scale(2), Rectangle::at(1, 2, 6, 8));
+        assert_eq!(rect.scale_xy(1, 0), Rectangle::at(1, 2, 3, 0));
+        assert_eq!(rect.to_string(), "3x4 at (1, 2)");
+    }
+
+    #[test]
+    #[should_panic(expected = "moved off the grid")]
+    fn translate_off_the_grid_panics() {
+        Rectangle::at(i32::MAX, 0, 1, 1).translate(1, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "scaled too tall")]
+    fn scale_past_u32_panics() {
+        Rectangle::new(1, u32::MAX / 2 + 1).scale(2);
+    }
+}
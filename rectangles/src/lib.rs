@@ -0,0 +1,11 @@
+//! Geometry for laying things out on a pixel grid.
+
+pub mod geometry;
+pub mod packing;
+pub mod scene;
+pub mod shapes;
+pub mod spatial;
+
+pub use geometry::{Point, Rectangle};
+pub use scene::Scene;
+pub use shapes::{Circle, Outline, Polygon, PolygonError, Shape, Triangle};
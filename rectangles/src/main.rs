@@ -1,40 +1,19 @@
-#[derive(Debug)]
-struct Rectangle {
-    width: u32,
-    height: u32,
-}
-
-// Define methods within scope of Rectangle struct
-// also called ASSOCIATED FUNCTIONS
-impl Rectangle {
-    // Need & in front of self since borrowing immutable
-    fn area(&self) -> u32 {
-        self.width * self.height
-    }
-
-    fn can_hold(&self, other: &Rectangle) -> bool {
-        self.width > other.width && self.height > other.height
-    }
-}
+use rectangles::packing::Packer;
+use rectangles::spatial::{QuadTree, SpatialIndex};
+use rectangles::{Circle, Point, Rectangle, Scene, Triangle};
 
-// Can have many impl blocks for same struct
-impl Rectangle {
-    // We can define associated functions that don’t have self
-    // as their first parameter (and thus are not methods) because
-    // they don’t need an instance of the type to work with.
-    fn square(size: u32) -> Self {
-        Rectangle {
-            width: size,
-            height: size,
-        }
-    }
-}
+// Rectangle and its methods live in the library crate now, see
+// src/geometry.rs. Methods are defined within scope of the Rectangle struct,
+// also called ASSOCIATED FUNCTIONS. Need & in front of self since borrowing
+// immutable, as in area() and can_hold().
+//
+// Can have many impl blocks for same struct. We can define associated
+// functions that don’t have self as their first parameter (and thus are not
+// methods) because they don’t need an instance of the type to work with,
+// like Rectangle::square.
 
 fn main() {
-    let rect1 = Rectangle {
-        width: 30,
-        height: 50,
-    };
+    let rect1 = Rectangle::new(30, 50);
 
     println!(
         "The area of the rectangle is {} square pixels (using function call).",
@@ -51,6 +30,7 @@ fn main() {
 
     let scale = 2;
     let rect2 = Rectangle {
+        origin: Point::new(10, 10),
         // dbg! macro takes and returns ownership
         width: dbg!(30 * scale),
         height: 60,
@@ -67,8 +47,56 @@ fn main() {
     let sq = Rectangle::square(3);
 
     println!("This is a square: {sq:#?}");
+
+    // Rectangles have a position too, so we can ask where they overlap
+    match rect1.intersection(&rect2) {
+        Some(overlap) => println!("rect1 and rect2 overlap in {overlap}"),
+        None => println!("rect1 and rect2 don't overlap"),
+    }
+    println!("Both fit inside {}", rect1.union(&rect2));
+
+    // Rectangle is one Shape among many, and a Scene can hold any mix of
+    // them as trait objects
+    let mut scene = Scene::new();
+    scene.add(rect1);
+    scene.add(Circle::new(Point::new(40, 40), 15));
+    scene.add(Triangle::new(
+        Point::new(100, 0),
+        Point::new(120, 0),
+        Point::new(100, 30),
+    ));
+
+    println!("The scene covers {:.1} square pixels", scene.total_area());
+    println!("Overlapping shapes: {:?}", scene.overlapping_pairs());
+
+    // can_hold at scale: pack a few rectangles into 16x8 bins, turning
+    // them sideways where that helps
+    let sprites = [
+        Rectangle::new(6, 4),
+        Rectangle::new(3, 7),
+        Rectangle::new(5, 5),
+        Rectangle::new(8, 2),
+        Rectangle::square(3),
+    ];
+    let packing = Packer::new(16, 8).with_rotation(true).pack(&sprites);
+    for (i, bin) in packing.bins().iter().enumerate() {
+        println!("Bin {i}, {} square pixels wasted:", bin.wasted_area());
+        print!("{}", bin.to_ascii(1));
+    }
+
+    // A spatial index finds nearby rectangles without checking every one
+    let mut index = QuadTree::new();
+    for placed in packing.bins()[0].placements() {
+        index.insert(placed.rect);
+    }
+    let click = Point::new(7, 3);
+    println!("Clicked on {:?}", index.at(click));
+    println!(
+        "Closest to the far corner: {:?}",
+        index.nearest(Point::new(15, 7))
+    );
 }
 
-fn area(rectangle: &Rectangle) -> u32 {
-    rectangle.width * rectangle.height
+fn area(rectangle: &Rectangle) -> u64 {
+    rectangle.width as u64 * rectangle.height as u64
 }
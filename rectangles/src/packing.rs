@@ -0,0 +1,434 @@
+//! Packs lists of rectangles into fixed-size bins, e.g. sprites into
+//! texture atlases.
+//!
+//! Items are placed without overlapping, opening a new bin whenever an item
+//! doesn't fit in any of the open ones. Items too big for an empty bin are
+//! reported as unplaced rather than failing the whole packing.
+
+mod maxrects;
+mod shelf;
+
+use crate::geometry::{Point, Rectangle};
+use maxrects::MaxRects;
+use shelf::Shelves;
+
+/// How free space in a bin is tracked and chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Algorithm {
+    /// Rows of items as tall as their tallest item. Fast, and wastes the
+    /// space above shorter items.
+    Shelf,
+    /// Keeps every maximal free rectangle and places each item where it
+    /// leaves the least space on its shorter side. Slower, packs tighter.
+    #[default]
+    MaxRects,
+}
+
+/// Where one item ended up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    /// The item's index in the list given to [`Packer::pack`].
+    pub item: usize,
+    /// The space the item takes in its bin, after any rotation.
+    pub rect: Rectangle,
+    /// True if the item was turned 90° to fit.
+    pub rotated: bool,
+}
+
+/// Settings for a packing run.
+///
+/// # Examples
+///
+/// ```
+/// use rectangles::Rectangle;
+/// use rectangles::packing::{Algorithm, Packer};
+///
+/// let sprites = [Rectangle::new(6, 2), Rectangle::new(2, 6), Rectangle::new(4, 4)];
+/// let packing = Packer::new(8, 8)
+///     .with_algorithm(Algorithm::Shelf)
+///     .with_rotation(true)
+///     .pack(&sprites);
+///
+/// assert_eq!(packing.bins().len(), 1);
+/// assert!(packing.unplaced().is_empty());
+/// assert_eq!(packing.wasted_area(), 64 - 12 - 12 - 16);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Packer {
+    width: u32,
+    height: u32,
+    algorithm: Algorithm,
+    rotation: bool,
+}
+
+impl Packer {
+    /// Packs into bins of the given size, using [`Algorithm::MaxRects`]
+    /// without rotation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either side is bigger than `i32::MAX`, since placements
+    /// couldn't be positioned on the grid.
+    pub fn new(width: u32, height: u32) -> Packer {
+        assert!(
+            i32::try_from(width).is_ok() && i32::try_from(height).is_ok(),
+            "bins can be at most {} wide and tall",
+            i32::MAX
+        );
+        Packer {
+            width,
+            height,
+            algorithm: Algorithm::default(),
+            rotation: false,
+        }
+    }
+
+    pub fn with_algorithm(mut self, algorithm: Algorithm) -> Packer {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Whether items may be turned 90° to fit better.
+    pub fn with_rotation(mut self, rotation: bool) -> Packer {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Places `items` into as many bins as it takes. Only the items'
+    /// sizes matter, their origins are ignored.
+    pub fn pack(&self, items: &[Rectangle]) -> Packing {
+        let bin = Rectangle::new(self.width, self.height);
+        let mut bins: Vec<(Bin, Box<dyn FreeSpace>)> = Vec::new();
+        let mut unplaced = Vec::new();
+
+        for item in self.order(items) {
+            let size = items[item];
+            if !self.fits_empty_bin(&bin, &size) {
+                unplaced.push(item);
+                continue;
+            }
+
+            let placed = bins.iter_mut().find_map(|(bin, space)| {
+                let (rect, rotated) = space.insert(size, self.rotation)?;
+                Some((bin, rect, rotated))
+            });
+            let (bin, rect, rotated) = match placed {
+                Some(placed) => placed,
+                None => {
+                    bins.push((Bin::new(self.width, self.height), self.free_space()));
+                    let (bin, space) = bins.last_mut().unwrap();
+                    let (rect, rotated) = space
+                        .insert(size, self.rotation)
+                        .expect("an item that fits an empty bin fits a new bin");
+                    (bin, rect, rotated)
+                }
+            };
+            bin.placements.push(Placement {
+                item,
+                rect,
+                rotated,
+            });
+        }
+
+        unplaced.sort_unstable();
+        Packing {
+            bins: bins.into_iter().map(|(bin, _)| bin).collect(),
+            unplaced,
+        }
+    }
+
+    // Both algorithms do best with the hardest items first. Tall items
+    // first keeps shelves tight; big items first gives MaxRects room to
+    // fit the small ones into the gaps.
+    fn order(&self, items: &[Rectangle]) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..items.len()).collect();
+        match self.algorithm {
+            Algorithm::Shelf => order.sort_by_key(|&i| {
+                let item = items[i];
+                let height = if self.rotation {
+                    item.width.min(item.height)
+                } else {
+                    item.height
+                };
+                std::cmp::Reverse((height, item.width.max(item.height)))
+            }),
+            Algorithm::MaxRects => order.sort_by_key(|&i| {
+                let item = items[i];
+                std::cmp::Reverse((item.width.max(item.height), item.area()))
+            }),
+        }
+        order
+    }
+
+    fn fits_empty_bin(&self, bin: &Rectangle, item: &Rectangle) -> bool {
+        if self.rotation {
+            bin.can_fit_rotated(item)
+        } else {
+            bin.can_fit(item)
+        }
+    }
+
+    fn free_space(&self) -> Box<dyn FreeSpace> {
+        match self.algorithm {
+            Algorithm::Shelf => Box::new(Shelves::new(self.width, self.height)),
+            Algorithm::MaxRects => Box::new(MaxRects::new(self.width, self.height)),
+        }
+    }
+}
+
+// The bookkeeping each algorithm keeps for one bin.
+trait FreeSpace {
+    // Finds room for an item of `size`, marks it as used and returns where
+    // it went and whether it had to be rotated.
+    fn insert(&mut self, size: Rectangle, rotation: bool) -> Option<(Rectangle, bool)>;
+}
+
+/// One bin and the items placed in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bin {
+    width: u32,
+    height: u32,
+    placements: Vec<Placement>,
+}
+
+impl Bin {
+    fn new(width: u32, height: u32) -> Bin {
+        Bin {
+            width,
+            height,
+            placements: Vec::new(),
+        }
+    }
+
+    /// The bin itself, with its origin at `(0, 0)`.
+    pub fn bounds(&self) -> Rectangle {
+        Rectangle::new(self.width, self.height)
+    }
+
+    /// Placements in the order they were made.
+    pub fn placements(&self) -> &[Placement] {
+        &self.placements
+    }
+
+    pub fn used_area(&self) -> u64 {
+        self.placements
+            .iter()
+            .map(|placed| placed.rect.area())
+            .sum()
+    }
+
+    pub fn wasted_area(&self) -> u64 {
+        self.bounds().area() - self.used_area()
+    }
+
+    /// Draws the bin as text, one character per `cell` × `cell` square.
+    /// Each item gets a letter or digit, picked by its index, and empty
+    /// space is `.`. A cell shows whatever covers its top-left corner.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cell` is zero.
+    pub fn to_ascii(&self, cell: u32) -> String {
+        assert!(cell > 0, "cells must be at least 1 unit wide");
+        const LABELS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+        let mut ascii = String::new();
+        for y in (0..self.height).step_by(cell as usize) {
+            for x in (0..self.width).step_by(cell as usize) {
+                let corner = Point::new(x as i32, y as i32);
+                let label = self
+                    .placements
+                    .iter()
+                    .find(|placed| placed.rect.contains_point(corner))
+                    .map_or('.', |placed| LABELS[placed.item % LABELS.len()] as char);
+                ascii.push(label);
+            }
+            ascii.push('\n');
+        }
+        ascii
+    }
+}
+
+/// The result of [`Packer::pack`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packing {
+    bins: Vec<Bin>,
+    unplaced: Vec<usize>,
+}
+
+impl Packing {
+    pub fn bins(&self) -> &[Bin] {
+        &self.bins
+    }
+
+    /// Indices of the items that are too big for an empty bin.
+    pub fn unplaced(&self) -> &[usize] {
+        &self.unplaced
+    }
+
+    /// Every placement, with the index of the bin it's in.
+    pub fn placements(&self) -> impl Iterator<Item = (usize, &Placement)> {
+        self.bins
+            .iter()
+            .enumerate()
+            .flat_map(|(i, bin)| bin.placements.iter().map(move |placed| (i, placed)))
+    }
+
+    /// Where an item ended up, if it was placed.
+    pub fn find(&self, item: usize) -> Option<(usize, &Placement)> {
+        self.placements().find(|(_, placed)| placed.item == item)
+    }
+
+    /// Unused area summed over every bin.
+    pub fn wasted_area(&self) -> u64 {
+        self.bins.iter().map(Bin::wasted_area).sum()
+    }
+
+    /// The fraction of the bins' area covered by items, from 0 to 1. An
+    /// empty packing counts as fully used.
+    pub fn utilisation(&self) -> f64 {
+        let total: u64 = self.bins.iter().map(|bin| bin.bounds().area()).sum();
+        if total == 0 {
+            return 1.0;
+        }
+        (total - self.wasted_area()) as f64 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_valid(packing: &Packing, items: &[Rectangle]) {
+        for bin in packing.bins() {
+            for (i, placed) in bin.placements().iter().enumerate() {
+                let item = items[placed.item];
+                let expected = if placed.rotated { item.rotated() } else { item };
+                assert_eq!(
+                    (placed.rect.width, placed.rect.height),
+                    (expected.width, expected.height)
+                );
+                assert!(
+                    bin.bounds().contains(&placed.rect),
+                    "{placed:?} is outside its bin"
+                );
+                for other in &bin.placements()[i + 1..] {
+                    assert!(
+                        !placed.rect.intersects(&other.rect),
+                        "{placed:?} overlaps {other:?}"
+                    );
+                }
+            }
+        }
+
+        let mut seen: Vec<_> = packing
+            .placements()
+            .map(|(_, placed)| placed.item)
+            .chain(packing.unplaced().iter().copied())
+            .collect();
+        seen.sort_unstable();
+        assert_eq!(seen, (0..items.len()).collect::<Vec<_>>());
+    }
+
+    fn items() -> Vec<Rectangle> {
+        [
+            (4, 3),
+            (2, 2),
+            (5, 1),
+            (1, 5),
+            (3, 3),
+            (2, 4),
+            (6, 2),
+            (1, 1),
+            (3, 2),
+        ]
+        .into_iter()
+        .map(|(w, h)| Rectangle::new(w, h))
+        .collect()
+    }
+
+    #[test]
+    fn every_algorithm_packs_without_overlaps() {
+        let items = items();
+        for algorithm in [Algorithm::Shelf, Algorithm::MaxRects] {
+            for rotation in [false, true] {
+                let packing = Packer::new(7, 7)
+                    .with_algorithm(algorithm)
+                    .with_rotation(rotation)
+                    .pack(&items);
+
+                check_valid(&packing, &items);
+                assert!(packing.unplaced().is_empty());
+                assert!(rotation || packing.placements().all(|(_, p)| !p.rotated));
+            }
+        }
+    }
+
+    #[test]
+    fn items_too_big_for_a_bin_are_unplaced() {
+        let items = [
+            Rectangle::new(2, 7),
+            Rectangle::new(3, 3),
+            Rectangle::new(9, 9),
+        ];
+
+        let packing = Packer::new(8, 4).pack(&items);
+        assert_eq!(packing.unplaced(), [0, 2]);
+        check_valid(&packing, &items);
+
+        let packing = Packer::new(8, 4).with_rotation(true).pack(&items);
+        assert_eq!(packing.unplaced(), [2]);
+        assert!(packing.find(0).unwrap().1.rotated);
+        check_valid(&packing, &items);
+    }
+
+    #[test]
+    fn opens_more_bins_as_needed() {
+        let items = vec![Rectangle::square(3); 5];
+
+        for algorithm in [Algorithm::Shelf, Algorithm::MaxRects] {
+            let packing = Packer::new(6, 6).with_algorithm(algorithm).pack(&items);
+
+            assert_eq!(packing.bins().len(), 2);
+            assert_eq!(packing.bins()[0].placements().len(), 4);
+            assert_eq!(packing.wasted_area(), 72 - 45);
+            check_valid(&packing, &items);
+        }
+    }
+
+    #[test]
+    fn perfect_fit_wastes_nothing() {
+        let items = [
+            Rectangle::new(4, 2),
+            Rectangle::new(2, 4),
+            Rectangle::new(2, 2),
+            Rectangle::new(2, 2),
+        ];
+        let packing = Packer::new(4, 6).with_rotation(true).pack(&items);
+
+        assert_eq!(packing.bins().len(), 1);
+        assert_eq!(packing.wasted_area(), 0);
+        assert_eq!(packing.utilisation(), 1.0);
+    }
+
+    #[test]
+    fn empty_input() {
+        let packing = Packer::new(10, 10).pack(&[]);
+
+        assert!(packing.bins().is_empty());
+        assert_eq!(packing.wasted_area(), 0);
+        assert_eq!(packing.utilisation(), 1.0);
+    }
+
+    #[test]
+    fn renders_placements_as_ascii() {
+        let items = [Rectangle::new(4, 2), Rectangle::new(2, 2)];
+        let packing = Packer::new(6, 3)
+            .with_algorithm(Algorithm::Shelf)
+            .pack(&items);
+        let bin = &packing.bins()[0];
+
+        assert_eq!(bin.to_ascii(1), "AAAABB\nAAAABB\n......\n");
+        assert_eq!(bin.to_ascii(2), "AAB\n...\n");
+    }
+}
@@ -0,0 +1,138 @@
+// MaxRects with the best short side fit heuristic (Jukka Jylänki, "A
+// Thousand Ways to Pack the Bin"). The free space is kept as every maximal
+// free rectangle, which may overlap each other. An item goes in the free
+// rectangle where it leaves the smallest gap along its shorter side, and
+// every free rectangle it overlaps is split around it.
+
+use super::FreeSpace;
+use crate::geometry::Rectangle;
+
+pub(super) struct MaxRects {
+    free: Vec<Rectangle>,
+}
+
+impl MaxRects {
+    pub(super) fn new(width: u32, height: u32) -> MaxRects {
+        MaxRects {
+            free: vec![Rectangle::new(width, height)],
+        }
+    }
+
+    fn split_free_space(&mut self, used: &Rectangle) {
+        let mut free = Vec::with_capacity(self.free.len() + 4);
+        for rect in &self.free {
+            if rect.intersects(used) {
+                free.extend(split(rect, used));
+            } else {
+                free.push(*rect);
+            }
+        }
+
+        // Drop anything inside another free rectangle: it's not maximal.
+        // Of two identical rectangles, keep the first.
+        let mut maximal: Vec<Rectangle> = Vec::with_capacity(free.len());
+        for (i, rect) in free.iter().enumerate() {
+            let redundant = free
+                .iter()
+                .enumerate()
+                .any(|(j, other)| i != j && other.contains(rect) && (other != rect || j < i));
+            if !redundant {
+                maximal.push(*rect);
+            }
+        }
+        self.free = maximal;
+    }
+}
+
+impl FreeSpace for MaxRects {
+    fn insert(&mut self, size: Rectangle, rotation: bool) -> Option<(Rectangle, bool)> {
+        let mut orientations = vec![(size, false)];
+        if rotation && size.width != size.height {
+            orientations.push((size.rotated(), true));
+        }
+
+        let (placed, rotated) = self
+            .free
+            .iter()
+            .flat_map(|free| {
+                orientations
+                    .iter()
+                    .map(move |&(item, rotated)| (free, item, rotated))
+            })
+            .filter(|(free, item, _)| free.can_fit(item))
+            .min_by_key(|(free, item, _)| {
+                let gap_x = free.width - item.width;
+                let gap_y = free.height - item.height;
+                (gap_x.min(gap_y), gap_x.max(gap_y))
+            })
+            .map(|(free, item, rotated)| (item.with_origin(free.origin), rotated))?;
+
+        self.split_free_space(&placed);
+        Some((placed, rotated))
+    }
+}
+
+// The up to four maximal pieces of `free` left around `used`: the strips
+// to its left, right, above and below, each as long as `free` itself.
+fn split(free: &Rectangle, used: &Rectangle) -> Vec<Rectangle> {
+    let mut pieces = Vec::with_capacity(4);
+    let (x, y) = (free.origin.x, free.origin.y);
+
+    if used.left() > free.left() {
+        pieces.push(Rectangle::at(
+            x,
+            y,
+            (used.left() - free.left()) as u32,
+            free.height,
+        ));
+    }
+    if used.right() < free.right() {
+        pieces.push(Rectangle::at(
+            used.right() as i32,
+            y,
+            (free.right() - used.right()) as u32,
+            free.height,
+        ));
+    }
+    if used.top() > free.top() {
+        pieces.push(Rectangle::at(
+            x,
+            y,
+            free.width,
+            (used.top() - free.top()) as u32,
+        ));
+    }
+    if used.bottom() < free.bottom() {
+        pieces.push(Rectangle::at(
+            x,
+            used.bottom() as i32,
+            free.width,
+            (free.bottom() - used.bottom()) as u32,
+        ));
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_around_a_used_corner() {
+        let pieces = split(&Rectangle::new(10, 10), &Rectangle::new(4, 3));
+
+        assert_eq!(
+            pieces,
+            [Rectangle::at(4, 0, 6, 10), Rectangle::at(0, 3, 10, 7)]
+        );
+    }
+
+    #[test]
+    fn free_space_stays_maximal() {
+        let mut space = MaxRects::new(10, 10);
+        space.insert(Rectangle::new(4, 3), false).unwrap();
+        space.insert(Rectangle::new(6, 3), false).unwrap();
+
+        assert_eq!(space.free, [Rectangle::at(0, 3, 10, 7)]);
+    }
+}
@@ -0,0 +1,82 @@
+// Shelf packing: the bin is split into horizontal shelves stacked from the
+// top. Each item goes on the first shelf with room for it, and a new shelf
+// as tall as the item is opened below the last one when none has room.
+
+use super::FreeSpace;
+use crate::geometry::Rectangle;
+
+pub(super) struct Shelves {
+    width: u32,
+    height: u32,
+    shelves: Vec<Shelf>,
+}
+
+struct Shelf {
+    y: u32,
+    height: u32,
+    // How much of the shelf's width is already used, from the left.
+    used: u32,
+}
+
+impl Shelves {
+    pub(super) fn new(width: u32, height: u32) -> Shelves {
+        Shelves {
+            width,
+            height,
+            shelves: Vec::new(),
+        }
+    }
+
+    fn place(&self, shelf: &Shelf, width: u32, height: u32) -> Rectangle {
+        // Bin sides fit in i32, so everything inside the bin does too.
+        Rectangle::at(shelf.used as i32, shelf.y as i32, width, height)
+    }
+}
+
+impl FreeSpace for Shelves {
+    fn insert(&mut self, size: Rectangle, rotation: bool) -> Option<(Rectangle, bool)> {
+        let orientations: &[(u32, u32, bool)] = if rotation {
+            &[
+                (size.width, size.height, false),
+                (size.height, size.width, true),
+            ]
+        } else {
+            &[(size.width, size.height, false)]
+        };
+
+        // On an existing shelf, use the orientation that leaves the least
+        // headroom above the item.
+        let best = self
+            .shelves
+            .iter()
+            .enumerate()
+            .flat_map(|(i, shelf)| orientations.iter().map(move |&o| (i, shelf, o)))
+            .filter(|&(_, shelf, (width, height, _))| {
+                height <= shelf.height && width <= self.width - shelf.used
+            })
+            .min_by_key(|&(i, shelf, (_, height, _))| (i, shelf.height - height));
+        if let Some((i, _, (width, height, rotated))) = best {
+            let rect = self.place(&self.shelves[i], width, height);
+            self.shelves[i].used += width;
+            return Some((rect, rotated));
+        }
+
+        // Otherwise open a new shelf, keeping it as low as the item allows.
+        let top = self
+            .shelves
+            .last()
+            .map_or(0, |shelf| shelf.y + shelf.height);
+        let &(width, height, rotated) = orientations
+            .iter()
+            .filter(|&&(width, height, _)| width <= self.width && height <= self.height - top)
+            .min_by_key(|&&(_, height, _)| height)?;
+        let shelf = Shelf {
+            y: top,
+            height,
+            used: width,
+        };
+        let rect = Rectangle::at(0, top as i32, width, height);
+        self.shelves.push(shelf);
+        Some((rect, rotated))
+    }
+}
@@ -0,0 +1,243 @@
+//! A mixed collection of shapes, and questions about how they overlap.
+
+use crate::geometry::{Point, Rectangle};
+use crate::shapes::Shape;
+
+/// Shapes of any kind, in the order they were added. Queries refer to
+/// shapes by that index.
+#[derive(Debug, Default)]
+pub struct Scene {
+    shapes: Vec<Box<dyn Shape>>,
+}
+
+impl Scene {
+    pub fn new() -> Scene {
+        Scene::default()
+    }
+
+    /// Adds a shape and returns its index.
+    pub fn add(&mut self, shape: impl Shape + 'static) -> usize {
+        self.shapes.push(Box::new(shape));
+        self.shapes.len() - 1
+    }
+
+    pub fn shapes(&self) -> &[Box<dyn Shape>] {
+        &self.shapes
+    }
+
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    /// The sum of every shape's area. Overlapping parts are counted once
+    /// per shape.
+    pub fn total_area(&self) -> f64 {
+        self.shapes.iter().map(|shape| shape.area()).sum()
+    }
+
+    /// The smallest rectangle holding every shape, or `None` for an empty
+    /// scene or one spread too far across the grid for a single rectangle
+    /// to cover.
+    pub fn bounding_box(&self) -> Option<Rectangle> {
+        let mut bounds = self.shapes.iter().map(|shape| shape.bounding_box());
+        let first = bounds.next()?;
+        bounds.try_fold(first, |all, bounds| all.checked_union(&bounds))
+    }
+
+    /// The indices of the shapes that contain `point`.
+    pub fn shapes_at(&self, point: Point) -> Vec<usize> {
+        self.indices_where(|shape| shape.contains_point(point))
+    }
+
+    /// The indices of the shapes whose bounding boxes intersect `region`.
+    pub fn shapes_near(&self, region: &Rectangle) -> Vec<usize> {
+        self.indices_where(|shape| shape.bounding_box().intersects(region))
+    }
+
+    /// Every pair of shapes that [`overlap`](overlaps), as `(earlier,
+    /// later)` indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rectangles::{Circle, Point, Rectangle, Scene};
+    ///
+    /// let mut scene = Scene::new();
+    /// scene.add(Rectangle::at(0, 0, 4, 4));
+    /// scene.add(Circle::new(Point::new(10, 10), 2));
+    /// scene.add(Circle::new(Point::new(4, 4), 2));
+    ///
+    /// assert_eq!(scene.overlapping_pairs(), [(0, 2)]);
+    /// ```
+    pub fn overlapping_pairs(&self) -> Vec<(usize, usize)> {
+        let bounds: Vec<_> = self
+            .shapes
+            .iter()
+            .map(|shape| shape.bounding_box())
+            .collect();
+        let mut pairs = Vec::new();
+
+        for (i, first) in self.shapes.iter().enumerate() {
+            for (j, second) in self.shapes.iter().enumerate().skip(i + 1) {
+                // Cheap rejection first: shapes can only overlap where their
+                // bounding boxes do.
+                if bounds[i].intersects(&bounds[j]) && first.outline().intersects(&second.outline())
+                {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        pairs
+    }
+
+    fn indices_where(&self, mut predicate: impl FnMut(&dyn Shape) -> bool) -> Vec<usize> {
+        self.shapes
+            .iter()
+            .enumerate()
+            .filter(|(_, shape)| predicate(&***shape))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+/// True if the two shapes share any point, including points between grid
+/// points and on their outlines. A rectangle stands for the grid points it
+/// contains, so two rectangles that only share an edge don't overlap.
+///
+/// This compares the shapes' [outlines](Shape::outline), so it takes time
+/// in proportion to their vertex counts multiplied, however big they are.
+pub fn overlaps(first: &dyn Shape, second: &dyn Shape) -> bool {
+    first.bounding_box().intersects(&second.bounding_box())
+        && first.outline().intersects(&second.outline())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::{Circle, Polygon, Triangle};
+
+    fn scene() -> Scene {
+        let mut scene = Scene::new();
+        scene.add(Rectangle::at(0, 0, 10, 10));
+        scene.add(Circle::new(Point::new(20, 5), 3));
+        scene.add(Triangle::new(
+            Point::new(9, 9),
+            Point::new(15, 9),
+            Point::new(9, 15),
+        ));
+        scene.add(
+            Polygon::new(vec![
+                Point::new(30, 0),
+                Point::new(34, 0),
+                Point::new(34, 4),
+                Point::new(30, 4),
+            ])
+            .unwrap(),
+        );
+        scene
+    }
+
+    #[test]
+    fn total_area_adds_up_every_shape() {
+        let expected = 100.0 + 9.0 * std::f64::consts::PI + 18.0 + 16.0;
+
+        assert!((scene().total_area() - expected).abs() < 1e-9);
+        assert_eq!(Scene::new().total_area(), 0.0);
+    }
+
+    #[test]
+    fn bounding_box_covers_the_scene() {
+        assert_eq!(scene().bounding_box(), Some(Rectangle::at(0, 0, 35, 16)));
+        assert_eq!(Scene::new().bounding_box(), None);
+    }
+
+    #[test]
+    fn bounding_box_too_big_for_the_grid() {
+        let mut scene = Scene::new();
+        scene.add(Rectangle::at(i32::MIN, 0, 1, 1));
+        scene.add(Circle::new(Point::new(0, 0), 1));
+        assert_eq!(
+            scene.bounding_box(),
+            Some(Rectangle::at(i32::MIN, -1, (1 << 31) + 2, 3))
+        );
+
+        scene.add(Rectangle::at(i32::MAX, 0, 1, 1));
+        assert_eq!(scene.bounding_box(), None);
+    }
+
+    #[test]
+    fn point_and_region_queries() {
+        let scene = scene();
+
+        assert_eq!(scene.shapes_at(Point::new(9, 9)), [0, 2]);
+        assert_eq!(scene.shapes_at(Point::new(20, 8)), [1]);
+        assert!(scene.shapes_at(Point::new(25, 5)).is_empty());
+        assert_eq!(scene.shapes_near(&Rectangle::at(18, 0, 20, 3)), [1, 3]);
+    }
+
+    #[test]
+    fn finds_overlapping_pairs() {
+        let mut scene = scene();
+        assert_eq!(scene.overlapping_pairs(), [(0, 2)]);
+
+        // Touches the circle's bounding box corner but not the circle.
+        let corner = scene.add(Rectangle::at(23, 7, 3, 3));
+        assert_eq!(scene.overlapping_pairs(), [(0, 2)]);
+
+        let through_everything = scene.add(Rectangle::at(0, 4, 40, 1));
+        assert_eq!(
+            scene.overlapping_pairs(),
+            [
+                (0, 2),
+                (0, through_everything),
+                (1, through_everything),
+                (3, through_everything),
+            ]
+        );
+        assert!(!overlaps(&*scene.shapes()[1], &*scene.shapes()[corner]));
+    }
+
+    #[test]
+    fn finds_overlaps_between_grid_points() {
+        // The two slivers cross around (0.5, 0.5), where there's no grid
+        // point.
+        let rising = Triangle::new(Point::new(0, 1), Point::new(1, 0), Point::new(1, 0));
+        let falling = Triangle::new(Point::new(0, 0), Point::new(1, 1), Point::new(1, 1));
+        assert!(overlaps(&rising, &falling));
+
+        // The triangle's only grid points are its vertices, all outside the
+        // circle, but its top edge dips into it around (0, 2.75).
+        let thin = Triangle::new(Point::new(-1, 3), Point::new(3, 2), Point::new(4, 2));
+        let circle = Circle::new(Point::new(0, 0), 3);
+        assert!(
+            thin.vertices
+                .iter()
+                .all(|&vertex| !circle.contains_point(vertex))
+        );
+        assert!(overlaps(&thin, &circle));
+    }
+
+    #[test]
+    fn huge_shapes_that_miss_each_other_are_quick() {
+        let far = 1 << 30;
+        let mut scene = Scene::new();
+        scene.add(Triangle::new(
+            Point::new(-far, -far),
+            Point::new(far, -far),
+            Point::new(-far, far),
+        ));
+        scene.add(Triangle::new(
+            Point::new(far, far),
+            Point::new(-far + 1, far),
+            Point::new(far, -far + 1),
+        ));
+        scene.add(Circle::new(Point::new(far, far), 1 << 29));
+
+        // Grid point by grid point this would take centuries.
+        assert_eq!(scene.overlapping_pairs(), [(1, 2)]);
+    }
+}
@@ -0,0 +1,615 @@
+//! Shapes other than rectangles, and the [`Shape`] trait they all share.
+//!
+//! Vertices and centers sit on the same integer grid as [`Rectangle`]s.
+//! Unlike rectangles, circles, triangles and polygons are closed: points on
+//! their outline count as inside.
+
+use crate::geometry::{Point, Rectangle};
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt;
+
+/// Anything that takes up space on the grid.
+pub trait Shape: fmt::Debug {
+    fn area(&self) -> f64;
+
+    fn perimeter(&self) -> f64;
+
+    /// The smallest rectangle holding every point the shape contains.
+    fn bounding_box(&self) -> Rectangle;
+
+    fn contains_point(&self, point: Point) -> bool;
+
+    /// The exact outline of the shape, for telling whether two shapes
+    /// overlap without testing point by point.
+    fn outline(&self) -> Outline;
+}
+
+/// A shape's outline, along with everything inside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outline {
+    /// Covers nothing, like a rectangle with no width.
+    Empty,
+    Circle(Circle),
+    /// A closed polygon through these vertices, in order. Vertices may
+    /// repeat or lie on a line, so this can be a segment or a single point.
+    Polygon(Vec<Point>),
+}
+
+impl Outline {
+    /// True if the two outlines share any point, inside or on the edge.
+    /// Unlike checking grid points, this also catches shapes that only
+    /// overlap between them.
+    pub fn intersects(&self, other: &Outline) -> bool {
+        match (self, other) {
+            (Outline::Empty, _) | (_, Outline::Empty) => false,
+            (Outline::Circle(a), Outline::Circle(b)) => {
+                let reach = i128::from(a.radius) + i128::from(b.radius);
+                distance_squared(a.center, b.center) <= reach * reach
+            }
+            (Outline::Circle(circle), Outline::Polygon(vertices))
+            | (Outline::Polygon(vertices), Outline::Circle(circle)) => {
+                polygon_contains(vertices, circle.center)
+                    || edges(vertices).any(|(a, b)| segment_within(a, b, *circle))
+            }
+            (Outline::Polygon(first), Outline::Polygon(second)) => {
+                // Either the outlines cross, or one is entirely inside the
+                // other and so are all of its vertices.
+                edges(first)
+                    .any(|(a, b)| edges(second).any(|(c, d)| segments_intersect(a, b, c, d)))
+                    || first.iter().any(|&vertex| polygon_contains(second, vertex))
+                    || second.iter().any(|&vertex| polygon_contains(first, vertex))
+            }
+        }
+    }
+}
+
+impl Shape for Rectangle {
+    fn area(&self) -> f64 {
+        Rectangle::area(self) as f64
+    }
+
+    fn perimeter(&self) -> f64 {
+        Rectangle::perimeter(self) as f64
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        *self
+    }
+
+    fn contains_point(&self, point: Point) -> bool {
+        Rectangle::contains_point(self, point)
+    }
+
+    // The grid points a rectangle contains run from its origin to one short
+    // of its right and bottom edges, so that's the outline.
+    fn outline(&self) -> Outline {
+        if self.is_empty() {
+            return Outline::Empty;
+        }
+        let (left, top) = (self.origin.x, self.origin.y);
+        let (right, bottom) = ((self.right() - 1) as i32, (self.bottom() - 1) as i32);
+        Outline::Polygon(vec![
+            Point::new(left, top),
+            Point::new(right, top),
+            Point::new(right, bottom),
+            Point::new(left, bottom),
+        ])
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Circle {
+    pub center: Point,
+    pub radius: u32,
+}
+
+impl Circle {
+    pub const fn new(center: Point, radius: u32) -> Circle {
+        Circle { center, radius }
+    }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        PI * f64::from(self.radius).powi(2)
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * PI * f64::from(self.radius)
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        let (x, y, r) = (
+            i64::from(self.center.x),
+            i64::from(self.center.y),
+            i64::from(self.radius),
+        );
+        bounds(x - r, y - r, x + r, y + r)
+    }
+
+    fn contains_point(&self, point: Point) -> bool {
+        distance_squared(self.center, point) <= i128::from(self.radius).pow(2)
+    }
+
+    fn outline(&self) -> Outline {
+        Outline::Circle(*self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Triangle {
+    pub vertices: [Point; 3],
+}
+
+impl Triangle {
+    pub const fn new(a: Point, b: Point, c: Point) -> Triangle {
+        Triangle {
+            vertices: [a, b, c],
+        }
+    }
+}
+
+impl Shape for Triangle {
+    fn area(&self) -> f64 {
+        let [a, b, c] = self.vertices;
+        cross(a, b, c).abs() as f64 / 2.0
+    }
+
+    fn perimeter(&self) -> f64 {
+        outline_length(&self.vertices)
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        bounding_box_of(&self.vertices)
+    }
+
+    fn contains_point(&self, point: Point) -> bool {
+        let [a, b, c] = self.vertices;
+        // With its vertices on a line, a triangle is just the segments
+        // between them; the sign test below would take in the whole line.
+        if cross(a, b, c) == 0 {
+            return on_segment(a, b, point) || on_segment(b, c, point) || on_segment(c, a, point);
+        }
+        let signs = [cross(a, b, point), cross(b, c, point), cross(c, a, point)];
+
+        // Inside (or on an edge) means never being on opposite sides of
+        // two edges, whichever way round the vertices go.
+        signs.iter().all(|&sign| sign >= 0) || signs.iter().all(|&sign| sign <= 0)
+    }
+
+    fn outline(&self) -> Outline {
+        Outline::Polygon(self.vertices.to_vec())
+    }
+}
+
+/// A simple polygon: its edges only meet at shared vertices.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+/// Why [`Polygon::new`] rejected a list of vertices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolygonError {
+    /// A polygon needs at least three vertices; this is how many it got.
+    TooFewVertices(usize),
+    /// Two edges cross, overlap or touch somewhere other than a shared
+    /// vertex.
+    NotSimple,
+}
+
+impl fmt::Display for PolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolygonError::TooFewVertices(count) => {
+                write!(f, "a polygon needs at least 3 vertices, got {count}")
+            }
+            PolygonError::NotSimple => write!(f, "the polygon's edges cross each other"),
+        }
+    }
+}
+
+impl Error for PolygonError {}
+
+impl Polygon {
+    /// Builds a polygon from its vertices in order, either clockwise or
+    /// anticlockwise. The last vertex joins back up with the first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rectangles::{Point, Polygon, PolygonError, Shape};
+    ///
+    /// let square = Polygon::new(vec![
+    ///     Point::new(0, 0),
+    ///     Point::new(4, 0),
+    ///     Point::new(4, 4),
+    ///     Point::new(0, 4),
+    /// ])
+    /// .unwrap();
+    /// assert_eq!(square.area(), 16.0);
+    ///
+    /// let bowtie = Polygon::new(vec![
+    ///     Point::new(0, 0),
+    ///     Point::new(4, 4),
+    ///     Point::new(4, 0),
+    ///     Point::new(0, 4),
+    /// ]);
+    /// assert_eq!(bowtie, Err(PolygonError::NotSimple));
+    /// ```
+    pub fn new(vertices: Vec<Point>) -> Result<Polygon, PolygonError> {
+        if vertices.len() < 3 {
+            return Err(PolygonError::TooFewVertices(vertices.len()));
+        }
+        if !is_simple(&vertices) {
+            return Err(PolygonError::NotSimple);
+        }
+        Ok(Polygon { vertices })
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        edges(&self.vertices)
+    }
+}
+
+impl Shape for Polygon {
+    // The shoelace formula: sum the cross products of consecutive vertices.
+    fn area(&self) -> f64 {
+        let origin = Point::default();
+        let twice_area: i128 = self.edges().map(|(a, b)| cross(origin, a, b)).sum();
+        twice_area.abs() as f64 / 2.0
+    }
+
+    fn perimeter(&self) -> f64 {
+        outline_length(&self.vertices)
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        bounding_box_of(&self.vertices)
+    }
+
+    fn contains_point(&self, point: Point) -> bool {
+        polygon_contains(&self.vertices, point)
+    }
+
+    fn outline(&self) -> Outline {
+        Outline::Polygon(self.vertices.clone())
+    }
+}
+
+// Cast a ray from the point towards +x and count the edges it crosses; an
+// odd count means the point is inside. Works for degenerate polygons too,
+// since a ray crosses a line of edges going out and coming back.
+fn polygon_contains(vertices: &[Point], point: Point) -> bool {
+    let mut inside = false;
+    for (a, b) in edges(vertices) {
+        if on_segment(a, b, point) {
+            return true;
+        }
+        if (a.y > point.y) != (b.y > point.y) {
+            // Which side of the edge the point is on tells us whether the
+            // crossing is to its right, without dividing.
+            let side = cross(a, b, point);
+            if (side > 0) == (b.y > a.y) {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+// Twice the signed area of the triangle o, a, b: positive if the turn from
+// a to b around o is anticlockwise (on a y-up grid). Coordinates are i32, so
+// the products need more than 64 bits.
+fn cross(o: Point, a: Point, b: Point) -> i128 {
+    let (ox, oy) = (i128::from(o.x), i128::from(o.y));
+    (i128::from(a.x) - ox) * (i128::from(b.y) - oy)
+        - (i128::from(a.y) - oy) * (i128::from(b.x) - ox)
+}
+
+fn dot(o: Point, a: Point, b: Point) -> i128 {
+    let (ox, oy) = (i128::from(o.x), i128::from(o.y));
+    (i128::from(a.x) - ox) * (i128::from(b.x) - ox)
+        + (i128::from(a.y) - oy) * (i128::from(b.y) - oy)
+}
+
+fn distance_squared(a: Point, b: Point) -> i128 {
+    dot(a, b, b)
+}
+
+// True if some point of the segment from a to b is inside the circle.
+fn segment_within(a: Point, b: Point, circle: Circle) -> bool {
+    let radius_squared = i128::from(circle.radius).pow(2);
+    if distance_squared(a, circle.center) <= radius_squared
+        || distance_squared(b, circle.center) <= radius_squared
+    {
+        return true;
+    }
+    // Unless the closest point lies strictly between the ends, it's one of
+    // the ends, which are both too far.
+    let along = dot(a, b, circle.center);
+    let length_squared = distance_squared(a, b);
+    if along <= 0 || along >= length_squared {
+        return false;
+    }
+    // The distance from the line is |cross| / length, so compare squares.
+    // Both sides can need more than 128 bits.
+    let cross = cross(a, b, circle.center).unsigned_abs();
+    wide_mul(cross, cross) <= wide_mul(radius_squared as u128, length_squared as u128)
+}
+
+// The full product of two u128s, as (high, low) halves.
+fn wide_mul(a: u128, b: u128) -> (u128, u128) {
+    const LOW: u128 = u64::MAX as u128;
+    let (a_high, a_low) = (a >> 64, a & LOW);
+    let (b_high, b_low) = (b >> 64, b & LOW);
+
+    let (middle, middle_carry) = (a_high * b_low).overflowing_add(a_low * b_high);
+    let (low, low_carry) = (a_low * b_low).overflowing_add(middle << 64);
+    let high =
+        a_high * b_high + (middle >> 64) + (u128::from(middle_carry) << 64) + u128::from(low_carry);
+    (high, low)
+}
+
+fn on_segment(a: Point, b: Point, point: Point) -> bool {
+    cross(a, b, point) == 0
+        && a.x.min(b.x) <= point.x
+        && point.x <= a.x.max(b.x)
+        && a.y.min(b.y) <= point.y
+        && point.y <= a.y.max(b.y)
+}
+
+fn segments_intersect(a: Point, b: Point, c: Point, d: Point) -> bool {
+    let (d1, d2) = (cross(c, d, a), cross(c, d, b));
+    let (d3, d4) = (cross(a, b, c), cross(a, b, d));
+    if ((d1 > 0 && d2 < 0) || (d1 < 0 && d2 > 0)) && ((d3 > 0 && d4 < 0) || (d3 < 0 && d4 > 0)) {
+        return true;
+    }
+    on_segment(c, d, a) || on_segment(c, d, b) || on_segment(a, b, c) || on_segment(a, b, d)
+}
+
+fn edges(vertices: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+fn is_simple(vertices: &[Point]) -> bool {
+    let edges: Vec<_> = edges(vertices).collect();
+    let n = edges.len();
+
+    for i in 0..n {
+        let (a, b) = edges[i];
+        if a == b {
+            return false;
+        }
+        // Neighbouring edges share a vertex, so they only go wrong by
+        // doubling back over each other.
+        let (_, next) = edges[(i + 1) % n];
+        if on_segment(a, b, next) || on_segment(b, next, a) {
+            return false;
+        }
+        // The last edge neighbours the first.
+        let end = if i == 0 { n - 1 } else { n };
+        for &(c, d) in edges.iter().take(end).skip(i + 2) {
+            if segments_intersect(a, b, c, d) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+fn outline_length(vertices: &[Point]) -> f64 {
+    edges(vertices)
+        .map(|(a, b)| {
+            let dx = f64::from(b.x) - f64::from(a.x);
+            let dy = f64::from(b.y) - f64::from(a.y);
+            dx.hypot(dy)
+        })
+        .sum()
+}
+
+fn bounding_box_of(vertices: &[Point]) -> Rectangle {
+    let xs = vertices.iter().map(|point| i64::from(point.x));
+    let ys = vertices.iter().map(|point| i64::from(point.y));
+    bounds(
+        xs.clone().min().unwrap(),
+        ys.clone().min().unwrap(),
+        xs.max().unwrap(),
+        ys.max().unwrap(),
+    )
+}
+
+// The rectangle covering every grid point from (left, top) to (right,
+// bottom) inclusive, cut down to what fits on the grid.
+fn bounds(left: i64, top: i64, right: i64, bottom: i64) -> Rectangle {
+    let clamp = |value: i64| value.clamp(i64::from(i32::MIN), i64::from(i32::MAX));
+    let (left, top, right, bottom) = (clamp(left), clamp(top), clamp(right), clamp(bottom));
+    let size = |from: i64, to: i64| u32::try_from(to - from + 1).unwrap_or(u32::MAX);
+
+    Rectangle::at(
+        left as i32,
+        top as i32,
+        size(left, right),
+        size(top, bottom),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(coordinates: &[(i32, i32)]) -> Vec<Point> {
+        coordinates.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    #[test]
+    fn circle_measurements() {
+        let circle = Circle::new(Point::new(2, 3), 2);
+
+        assert!((circle.area() - 4.0 * PI).abs() < 1e-9);
+        assert!((circle.perimeter() - 4.0 * PI).abs() < 1e-9);
+        assert_eq!(circle.bounding_box(), Rectangle::at(0, 1, 5, 5));
+        assert!(circle.contains_point(Point::new(4, 3)));
+        assert!(!circle.contains_point(Point::new(4, 4)));
+    }
+
+    #[test]
+    fn huge_circles_are_clamped_to_the_grid() {
+        let circle = Circle::new(Point::new(i32::MAX, i32::MIN), u32::MAX);
+        let bounds = circle.bounding_box();
+
+        assert_eq!(bounds.origin, Point::new(i32::MIN, i32::MIN));
+        assert!(circle.contains_point(Point::new(0, 0)));
+    }
+
+    #[test]
+    fn triangle_works_in_either_winding() {
+        let clockwise = Triangle::new(Point::new(0, 0), Point::new(4, 0), Point::new(0, 3));
+        let [a, b, c] = clockwise.vertices;
+        let anticlockwise = Triangle::new(a, c, b);
+
+        for triangle in [clockwise, anticlockwise] {
+            assert_eq!(triangle.area(), 6.0);
+            assert_eq!(triangle.perimeter(), 12.0);
+            assert_eq!(triangle.bounding_box(), Rectangle::at(0, 0, 5, 4));
+            assert!(triangle.contains_point(Point::new(1, 1)));
+            assert!(triangle.contains_point(Point::new(4, 0)));
+            assert!(!triangle.contains_point(Point::new(3, 2)));
+        }
+    }
+
+    #[test]
+    fn collinear_triangles_stop_at_their_ends() {
+        let flat = Triangle::new(Point::new(0, 0), Point::new(1, 0), Point::new(2, 0));
+        let bounds = flat.bounding_box();
+
+        assert_eq!(flat.area(), 0.0);
+        assert_eq!(bounds, Rectangle::at(0, 0, 3, 1));
+        for x in 0..=2 {
+            assert!(flat.contains_point(Point::new(x, 0)));
+        }
+        for point in [(1000, 0), (3, 0), (-1, 0), (1, 1)] {
+            let point = Point::new(point.0, point.1);
+            assert!(!flat.contains_point(point), "{point}");
+            assert!(!bounds.contains_point(point), "{point}");
+        }
+
+        let diagonal = Triangle::new(Point::new(4, 4), Point::new(0, 0), Point::new(2, 2));
+        assert!(diagonal.contains_point(Point::new(1, 1)));
+        assert!(!diagonal.contains_point(Point::new(5, 5)));
+        assert!(!diagonal.contains_point(Point::new(1, 2)));
+    }
+
+    #[test]
+    fn outlines_touching_at_a_point_intersect() {
+        let circle = Circle::new(Point::new(0, 0), 5).outline();
+        let touching = Triangle::new(Point::new(5, 0), Point::new(9, 0), Point::new(9, 9));
+        let apart = Triangle::new(Point::new(4, 4), Point::new(9, 4), Point::new(9, 9));
+
+        assert!(circle.intersects(&touching.outline()));
+        assert!(!circle.intersects(&apart.outline()));
+        assert!(
+            Circle::new(Point::new(10, 0), 5)
+                .outline()
+                .intersects(&circle)
+        );
+        assert!(
+            !Circle::new(Point::new(11, 0), 5)
+                .outline()
+                .intersects(&circle)
+        );
+        assert!(!Rectangle::new(0, 5).outline().intersects(&circle));
+    }
+
+    #[test]
+    fn edges_near_huge_circles_need_wide_products() {
+        // The closest point of the edge is far from either end, exactly
+        // u32::MAX below the center, and the squares overflow an i128.
+        let edge = (
+            Point::new(i32::MIN, i32::MAX),
+            Point::new(i32::MAX, i32::MAX),
+        );
+        let far = Circle::new(Point::new(0, i32::MIN), u32::MAX - 1);
+        let near = Circle::new(Point::new(0, i32::MIN), u32::MAX);
+
+        assert!(!segment_within(edge.0, edge.1, far));
+        assert!(segment_within(edge.0, edge.1, near));
+        assert_eq!(wide_mul(u128::MAX, u128::MAX), (u128::MAX - 1, 1));
+    }
+
+    #[test]
+    fn concave_polygon() {
+        // An L shape with the notch in the top right.
+        let l = Polygon::new(points(&[(0, 0), (2, 0), (2, 2), (4, 2), (4, 4), (0, 4)])).unwrap();
+
+        assert_eq!(l.area(), 12.0);
+        assert_eq!(l.perimeter(), 16.0);
+        assert_eq!(l.bounding_box(), Rectangle::at(0, 0, 5, 5));
+        assert!(l.contains_point(Point::new(1, 1)));
+        assert!(l.contains_point(Point::new(3, 3)));
+        assert!(l.contains_point(Point::new(3, 2)));
+        assert!(!l.contains_point(Point::new(3, 1)));
+        assert!(!l.contains_point(Point::new(5, 3)));
+    }
+
+    #[test]
+    fn ray_through_a_vertex_counts_once() {
+        let diamond = Polygon::new(points(&[(2, 0), (4, 2), (2, 4), (0, 2)])).unwrap();
+
+        assert!(diamond.contains_point(Point::new(1, 2)));
+        assert!(!diamond.contains_point(Point::new(-1, 2)));
+        assert!(!diamond.contains_point(Point::new(-1, 0)));
+    }
+
+    #[test]
+    fn rejects_polygons_that_are_not_simple() {
+        assert_eq!(
+            Polygon::new(points(&[(0, 0), (1, 1)])),
+            Err(PolygonError::TooFewVertices(2))
+        );
+        assert_eq!(
+            Polygon::new(points(&[(0, 0), (2, 0), (1, 0)])),
+            Err(PolygonError::NotSimple)
+        );
+        assert_eq!(
+            Polygon::new(points(&[(0, 0), (2, 0), (2, 0), (0, 2)])),
+            Err(PolygonError::NotSimple)
+        );
+        // Two squares touching at a corner.
+        assert_eq!(
+            Polygon::new(points(&[
+                (0, 0),
+                (2, 0),
+                (2, 2),
+                (4, 2),
+                (4, 4),
+                (2, 4),
+                (2, 2),
+                (0, 2)
+            ])),
+            Err(PolygonError::NotSimple)
+        );
+        assert_eq!(
+            PolygonError::TooFewVertices(1).to_string(),
+            "a polygon needs at least 3 vertices, got 1"
+        );
+    }
+
+    #[test]
+    fn rectangles_are_shapes() {
+        let rect: &dyn Shape = &Rectangle::at(1, 1, 2, 3);
+
+        assert_eq!(rect.area(), 6.0);
+        assert_eq!(rect.perimeter(), 10.0);
+        assert!(rect.contains_point(Point::new(2, 3)));
+        assert!(!rect.contains_point(Point::new(3, 3)));
+    }
+}
@@ -0,0 +1,187 @@
+//! Indexes for finding rectangles by position without checking every one.
+//!
+//! [`QuadTree`] is the index to use; [`BruteForce`] answers the same
+//! questions by checking everything, and is there as a reference to test
+//! against.
+
+mod quadtree;
+
+pub use quadtree::QuadTree;
+
+use crate::geometry::{Point, Rectangle};
+
+/// Identifies a rectangle inside an index. Ids are handed out by
+/// [`SpatialIndex::insert`] in increasing order and never reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ItemId(usize);
+
+/// A collection of rectangles that can be searched by position.
+///
+/// Queries return ids in increasing order, so different indexes holding
+/// the same rectangles give the same answers.
+pub trait SpatialIndex {
+    fn insert(&mut self, rect: Rectangle) -> ItemId;
+
+    /// Removes a rectangle, returning it if it was in the index.
+    fn remove(&mut self, id: ItemId) -> Option<Rectangle>;
+
+    fn get(&self, id: ItemId) -> Option<Rectangle>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Every rectangle with its id, in id order.
+    fn items(&self) -> Vec<(ItemId, Rectangle)>;
+
+    /// The rectangles that intersect `region`.
+    fn query(&self, region: &Rectangle) -> Vec<ItemId>;
+
+    /// The rectangles that contain `point`.
+    fn at(&self, point: Point) -> Vec<ItemId>;
+
+    /// The rectangle closest to `point`, measured to the nearest grid point
+    /// it covers. Ties go to the lowest id, and empty rectangles, which
+    /// cover no points, are never picked.
+    fn nearest(&self, point: Point) -> Option<ItemId>;
+
+    /// Every pair of intersecting rectangles, as `(lower, higher)` ids.
+    fn overlapping_pairs(&self) -> Vec<(ItemId, ItemId)> {
+        let mut pairs = Vec::new();
+        for (id, rect) in self.items() {
+            for other in self.query(&rect) {
+                if id < other {
+                    pairs.push((id, other));
+                }
+            }
+        }
+        pairs
+    }
+}
+
+/// Checks every rectangle for every query.
+#[derive(Debug, Clone, Default)]
+pub struct BruteForce {
+    // Indexed by id; removed rectangles leave a `None` behind.
+    rects: Vec<Option<Rectangle>>,
+    len: usize,
+}
+
+impl BruteForce {
+    pub fn new() -> BruteForce {
+        BruteForce::default()
+    }
+
+    fn matching(&self, predicate: impl Fn(&Rectangle) -> bool) -> Vec<ItemId> {
+        self.items()
+            .into_iter()
+            .filter(|(_, rect)| predicate(rect))
+            .map(|(id, _)| id)
+            .collect()
+    }
+}
+
+impl SpatialIndex for BruteForce {
+    fn insert(&mut self, rect: Rectangle) -> ItemId {
+        self.rects.push(Some(rect));
+        self.len += 1;
+        ItemId(self.rects.len() - 1)
+    }
+
+    fn remove(&mut self, id: ItemId) -> Option<Rectangle> {
+        let removed = self.rects.get_mut(id.0)?.take()?;
+        self.len -= 1;
+        Some(removed)
+    }
+
+    fn get(&self, id: ItemId) -> Option<Rectangle> {
+        *self.rects.get(id.0)?
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn items(&self) -> Vec<(ItemId, Rectangle)> {
+        self.rects
+            .iter()
+            .enumerate()
+            .filter_map(|(i, rect)| Some((ItemId(i), (*rect)?)))
+            .collect()
+    }
+
+    fn query(&self, region: &Rectangle) -> Vec<ItemId> {
+        self.matching(|rect| rect.intersects(region))
+    }
+
+    fn at(&self, point: Point) -> Vec<ItemId> {
+        self.matching(|rect| rect.contains_point(point))
+    }
+
+    fn nearest(&self, point: Point) -> Option<ItemId> {
+        self.items()
+            .into_iter()
+            .filter(|(_, rect)| !rect.is_empty())
+            .min_by_key(|(id, rect)| (distance_squared(rect, point), *id))
+            .map(|(id, _)| id)
+    }
+}
+
+// Squared distance from `point` to the closest grid point the non-empty
+// rectangle covers. A gap can be nearly 2^32, so its square needs u128.
+fn distance_squared(rect: &Rectangle, point: Point) -> u128 {
+    edge_distance_squared(rect.left(), rect.top(), rect.right(), rect.bottom(), point)
+}
+
+// The same for the half-open area [left, right) × [top, bottom), which
+// mustn't be empty.
+fn edge_distance_squared(left: i64, top: i64, right: i64, bottom: i64, point: Point) -> u128 {
+    let gap = |low: i64, high: i64, at: i64| (low - at).max(at - (high - 1)).max(0) as u128;
+    let dx = gap(left, right, i64::from(point.x));
+    let dy = gap(top, bottom, i64::from(point.y));
+    dx * dx + dy * dy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_is_zero_inside() {
+        let rect = Rectangle::at(0, 0, 4, 4);
+
+        assert_eq!(distance_squared(&rect, Point::new(3, 3)), 0);
+        assert_eq!(distance_squared(&rect, Point::new(4, 3)), 1);
+        assert_eq!(distance_squared(&rect, Point::new(-3, 7)), 9 + 16);
+        assert_eq!(
+            distance_squared(&Rectangle::at(i32::MAX, 0, 1, 1), Point::new(i32::MIN, 0)),
+            (u32::MAX as u128).pow(2)
+        );
+    }
+
+    #[test]
+    fn brute_force_ids_are_never_reused() {
+        let mut index = BruteForce::new();
+        let first = index.insert(Rectangle::new(1, 1));
+        assert_eq!(index.remove(first), Some(Rectangle::new(1, 1)));
+        assert_eq!(index.remove(first), None);
+
+        let second = index.insert(Rectangle::new(2, 2));
+        assert_ne!(first, second);
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.get(first), None);
+    }
+
+    #[test]
+    fn overlapping_pairs_are_ordered() {
+        let mut index = BruteForce::new();
+        let a = index.insert(Rectangle::at(0, 0, 4, 4));
+        index.insert(Rectangle::at(10, 10, 1, 1));
+        let c = index.insert(Rectangle::at(3, 3, 4, 4));
+        let d = index.insert(Rectangle::at(2, 0, 1, 10));
+
+        assert_eq!(index.overlapping_pairs(), [(a, c), (a, d)]);
+    }
+}
@@ -0,0 +1,364 @@
+// A region quadtree over the whole i32 grid. Each node holds the rectangles
+// that don't fit entirely inside one of its four quarters; a leaf splits
+// once it holds more than CAPACITY of them, and children are merged back
+// when removals leave a subtree small enough to be a leaf again.
+
+use super::{ItemId, SpatialIndex, edge_distance_squared};
+use crate::geometry::{Point, Rectangle};
+use std::collections::HashMap;
+
+const CAPACITY: usize = 8;
+// Past this depth nodes are a single grid point wide and can't split.
+const MAX_DEPTH: u32 = 32;
+
+/// A [`SpatialIndex`] that splits the grid into quarters wherever
+/// rectangles are crowded, so queries only look at nearby rectangles.
+///
+/// # Examples
+///
+/// ```
+/// use rectangles::spatial::{QuadTree, SpatialIndex};
+/// use rectangles::{Point, Rectangle};
+///
+/// let mut index = QuadTree::new();
+/// let near = index.insert(Rectangle::at(0, 0, 10, 10));
+/// let far = index.insert(Rectangle::at(1000, 1000, 10, 10));
+///
+/// assert_eq!(index.query(&Rectangle::at(5, 5, 100, 100)), [near]);
+/// assert_eq!(index.at(Point::new(1005, 1009)), [far]);
+/// assert_eq!(index.nearest(Point::new(900, 900)), Some(far));
+/// ```
+#[derive(Debug, Clone)]
+pub struct QuadTree {
+    root: Node,
+    // Where each rectangle is, so removal can find its node.
+    rects: HashMap<ItemId, Rectangle>,
+    next_id: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    bounds: Bounds,
+    items: Vec<(ItemId, Rectangle)>,
+    children: Option<Box<[Node; 4]>>,
+    // Rectangles in this node and everything below it.
+    count: usize,
+}
+
+// A square [left, left + size) × [top, top + size). The root is 2^32 wide,
+// which is why this isn't a Rectangle.
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+    left: i64,
+    top: i64,
+    size: i64,
+}
+
+impl Bounds {
+    fn contains(&self, rect: &Rectangle) -> bool {
+        self.left <= rect.left()
+            && rect.right() <= self.left + self.size
+            && self.top <= rect.top()
+            && rect.bottom() <= self.top + self.size
+    }
+
+    fn intersects(&self, rect: &Rectangle) -> bool {
+        !rect.is_empty()
+            && rect.left() < self.left + self.size
+            && self.left < rect.right()
+            && rect.top() < self.top + self.size
+            && self.top < rect.bottom()
+    }
+
+    fn contains_point(&self, point: Point) -> bool {
+        let (x, y) = (i64::from(point.x), i64::from(point.y));
+        self.left <= x && x < self.left + self.size && self.top <= y && y < self.top + self.size
+    }
+
+    fn distance_squared(&self, point: Point) -> u128 {
+        edge_distance_squared(
+            self.left,
+            self.top,
+            self.left + self.size,
+            self.top + self.size,
+            point,
+        )
+    }
+
+    fn quarters(&self) -> [Bounds; 4] {
+        let size = self.size / 2;
+        let quarter = |left, top| Bounds { left, top, size };
+        [
+            quarter(self.left, self.top),
+            quarter(self.left + size, self.top),
+            quarter(self.left, self.top + size),
+            quarter(self.left + size, self.top + size),
+        ]
+    }
+}
+
+impl Node {
+    fn new(bounds: Bounds) -> Node {
+        Node {
+            bounds,
+            items: Vec::new(),
+            children: None,
+            count: 0,
+        }
+    }
+
+    // The child a rectangle belongs in, if it fits inside one.
+    fn child_for(&mut self, rect: &Rectangle) -> Option<&mut Node> {
+        self.children
+            .as_deref_mut()?
+            .iter_mut()
+            .find(|child| child.bounds.contains(rect))
+    }
+
+    fn insert(&mut self, id: ItemId, rect: Rectangle, depth: u32) {
+        self.count += 1;
+        if let Some(child) = self.child_for(&rect) {
+            child.insert(id, rect, depth + 1);
+            return;
+        }
+
+        self.items.push((id, rect));
+        if self.children.is_none() && self.items.len() > CAPACITY && depth < MAX_DEPTH {
+            self.split(depth);
+        }
+    }
+
+    fn split(&mut self, depth: u32) {
+        self.children = Some(Box::new(self.bounds.quarters().map(Node::new)));
+        for (id, rect) in std::mem::take(&mut self.items) {
+            match self.child_for(&rect) {
+                Some(child) => child.insert(id, rect, depth + 1),
+                None => self.items.push((id, rect)),
+            }
+        }
+    }
+
+    fn remove(&mut self, id: ItemId, rect: &Rectangle) {
+        self.count -= 1;
+        match self.child_for(rect) {
+            Some(child) => child.remove(id, rect),
+            None => {
+                let i = self
+                    .items
+                    .iter()
+                    .position(|&(item, _)| item == id)
+                    .expect("rectangles are stored in the node they fit");
+                self.items.swap_remove(i);
+            }
+        }
+
+        if self.children.is_some() && self.count <= CAPACITY {
+            let mut items = std::mem::take(&mut self.items);
+            self.collect(&mut items);
+            self.items = items;
+            self.children = None;
+        }
+    }
+
+    // Appends every rectangle below this node.
+    fn collect(&self, items: &mut Vec<(ItemId, Rectangle)>) {
+        for child in self.children() {
+            items.extend_from_slice(&child.items);
+            child.collect(items);
+        }
+    }
+
+    fn children(&self) -> impl Iterator<Item = &Node> {
+        self.children.iter().flat_map(|children| children.iter())
+    }
+
+    fn query(&self, region: &Rectangle, found: &mut Vec<ItemId>) {
+        if !self.bounds.intersects(region) {
+            return;
+        }
+        found.extend(
+            self.items
+                .iter()
+                .filter(|(_, rect)| rect.intersects(region))
+                .map(|&(id, _)| id),
+        );
+        for child in self.children() {
+            child.query(region, found);
+        }
+    }
+
+    fn at(&self, point: Point, found: &mut Vec<ItemId>) {
+        if !self.bounds.contains_point(point) {
+            return;
+        }
+        found.extend(
+            self.items
+                .iter()
+                .filter(|(_, rect)| rect.contains_point(point))
+                .map(|&(id, _)| id),
+        );
+        for child in self.children() {
+            child.at(point, found);
+        }
+    }
+
+    // Branch and bound: skip any node that can't hold anything closer than
+    // the best match so far, and visit the closest children first so the
+    // best match improves quickly.
+    fn nearest(&self, point: Point, best: &mut Option<(u128, ItemId)>) {
+        for &(id, rect) in &self.items {
+            if rect.is_empty() {
+                continue;
+            }
+            let candidate = (super::distance_squared(&rect, point), id);
+            if best.is_none_or(|best| candidate < best) {
+                *best = Some(candidate);
+            }
+        }
+
+        let mut children: Vec<_> = self
+            .children()
+            .filter(|child| child.count > 0)
+            .map(|child| (child.bounds.distance_squared(point), child))
+            .collect();
+        children.sort_by_key(|&(distance, _)| distance);
+        for (distance, child) in children {
+            if best.is_some_and(|(best, _)| distance > best) {
+                break;
+            }
+            child.nearest(point, best);
+        }
+    }
+}
+
+impl QuadTree {
+    pub fn new() -> QuadTree {
+        let grid = Bounds {
+            left: i64::from(i32::MIN),
+            top: i64::from(i32::MIN),
+            size: 1 << 32,
+        };
+        QuadTree {
+            root: Node::new(grid),
+            rects: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// How many levels the tree has, counting the root.
+    pub fn depth(&self) -> usize {
+        fn depth(node: &Node) -> usize {
+            1 + node.children().map(depth).max().unwrap_or(0)
+        }
+        depth(&self.root)
+    }
+}
+
+impl Default for QuadTree {
+    fn default() -> QuadTree {
+        QuadTree::new()
+    }
+}
+
+impl SpatialIndex for QuadTree {
+    fn insert(&mut self, rect: Rectangle) -> ItemId {
+        let id = ItemId(self.next_id);
+        self.next_id += 1;
+        self.root.insert(id, rect, 0);
+        self.rects.insert(id, rect);
+        id
+    }
+
+    fn remove(&mut self, id: ItemId) -> Option<Rectangle> {
+        let rect = self.rects.remove(&id)?;
+        self.root.remove(id, &rect);
+        Some(rect)
+    }
+
+    fn get(&self, id: ItemId) -> Option<Rectangle> {
+        self.rects.get(&id).copied()
+    }
+
+    fn len(&self) -> usize {
+        self.rects.len()
+    }
+
+    fn items(&self) -> Vec<(ItemId, Rectangle)> {
+        let mut items: Vec<_> = self.rects.iter().map(|(&id, &rect)| (id, rect)).collect();
+        items.sort_unstable_by_key(|&(id, _)| id);
+        items
+    }
+
+    fn query(&self, region: &Rectangle) -> Vec<ItemId> {
+        let mut found = Vec::new();
+        self.root.query(region, &mut found);
+        found.sort_unstable();
+        found
+    }
+
+    fn at(&self, point: Point) -> Vec<ItemId> {
+        let mut found = Vec::new();
+        self.root.at(point, &mut found);
+        found.sort_unstable();
+        found
+    }
+
+    fn nearest(&self, point: Point) -> Option<ItemId> {
+        let mut best = None;
+        self.root.nearest(point, &mut best);
+        best.map(|(_, id)| id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_when_crowded_and_merges_when_emptied() {
+        let mut index = QuadTree::new();
+        let ids: Vec<_> = (0..=CAPACITY as i32)
+            .map(|i| index.insert(Rectangle::at(i * 10, i * 10, 5, 5)))
+            .collect();
+        assert!(index.depth() > 1);
+
+        index.remove(ids[0]);
+        assert_eq!(index.depth(), 1);
+        assert_eq!(index.root.items.len(), CAPACITY);
+        assert_eq!(index.len(), CAPACITY);
+    }
+
+    #[test]
+    fn identical_rectangles_stop_splitting() {
+        let mut index = QuadTree::new();
+        for _ in 0..100 {
+            index.insert(Rectangle::at(7, 7, 1, 1));
+        }
+
+        assert!(index.depth() <= MAX_DEPTH as usize + 1);
+        assert_eq!(index.at(Point::new(7, 7)).len(), 100);
+    }
+
+    #[test]
+    fn rectangles_across_the_middle_stay_at_the_root() {
+        let mut index = QuadTree::new();
+        for i in 0..20 {
+            index.insert(Rectangle::at(-1, i, 2, 1));
+        }
+
+        assert_eq!(index.root.items.len(), 20);
+        assert_eq!(index.query(&Rectangle::at(0, 5, 1, 3)).len(), 3);
+    }
+
+    #[test]
+    fn works_at_the_edges_of_the_grid() {
+        let mut index = QuadTree::new();
+        let corner = index.insert(Rectangle::at(i32::MIN, i32::MIN, 1, 1));
+        let other = index.insert(Rectangle::at(i32::MAX, i32::MAX, u32::MAX, 1));
+
+        assert_eq!(index.at(Point::new(i32::MIN, i32::MIN)), [corner]);
+        assert_eq!(index.at(Point::new(i32::MAX, i32::MAX)), [other]);
+        assert_eq!(index.nearest(Point::new(-1, -1)), Some(corner));
+        assert_eq!(index.nearest(Point::new(0, 0)), Some(other));
+    }
+}
@@ -0,0 +1,75 @@
+use proptest::prelude::*;
+use rectangles::Rectangle;
+use rectangles::packing::{Algorithm, Packer, Packing};
+
+fn items() -> impl Strategy<Value = Vec<Rectangle>> {
+    prop::collection::vec(
+        (0..40u32, 0..40u32).prop_map(|(width, height)| Rectangle::new(width, height)),
+        0..60,
+    )
+}
+
+fn algorithm() -> impl Strategy<Value = Algorithm> {
+    prop_oneof![Just(Algorithm::Shelf), Just(Algorithm::MaxRects)]
+}
+
+fn check(packing: &Packing, items: &[Rectangle], bin: Rectangle, rotation: bool) {
+    let mut seen = vec![false; items.len()];
+
+    for placed_bin in packing.bins() {
+        assert!(
+            !placed_bin.placements().is_empty(),
+            "bins are never left empty"
+        );
+        for (i, placed) in placed_bin.placements().iter().enumerate() {
+            let item = items[placed.item];
+            let size = if placed.rotated { item.rotated() } else { item };
+            assert!(rotation || !placed.rotated);
+            assert_eq!(
+                (placed.rect.width, placed.rect.height),
+                (size.width, size.height)
+            );
+            assert!(bin.contains(&placed.rect));
+            for other in &placed_bin.placements()[i + 1..] {
+                assert!(!placed.rect.intersects(&other.rect));
+            }
+            assert!(!seen[placed.item], "item {} placed twice", placed.item);
+            seen[placed.item] = true;
+        }
+    }
+
+    for &item in packing.unplaced() {
+        assert!(!seen[item]);
+        let size = items[item];
+        let fits = if rotation {
+            bin.can_fit_rotated(&size)
+        } else {
+            bin.can_fit(&size)
+        };
+        assert!(!fits, "item {item} fits an empty bin but wasn't placed");
+        seen[item] = true;
+    }
+    assert!(seen.into_iter().all(|seen| seen));
+}
+
+proptest! {
+    #[test]
+    fn packings_are_valid(
+        items in items(),
+        algorithm in algorithm(),
+        rotation in any::<bool>(),
+        width in 1..64u32,
+        height in 1..64u32,
+    ) {
+        let packing = Packer::new(width, height)
+            .with_algorithm(algorithm)
+            .with_rotation(rotation)
+            .pack(&items);
+
+        check(&packing, &items, Rectangle::new(width, height), rotation);
+
+        let used: u64 = packing.bins().iter().map(|bin| bin.used_area()).sum();
+        let total = packing.bins().len() as u64 * u64::from(width * height);
+        prop_assert_eq!(used + packing.wasted_area(), total);
+    }
+}
@@ -0,0 +1,223 @@
+use proptest::prelude::*;
+use rectangles::scene::overlaps;
+use rectangles::{Circle, Point, Polygon, Rectangle, Shape, Triangle};
+
+// Keep coordinates small enough that translating and scaling can't
+// overflow, but let rectangles be empty.
+fn point() -> impl Strategy<Value = Point> {
+    (-1000..1000, -1000..1000).prop_map(|(x, y)| Point::new(x, y))
+}
+
+fn rectangle() -> impl Strategy<Value = Rectangle> {
+    (point(), 0..500u32, 0..500u32).prop_map(|(origin, width, height)| Rectangle {
+        origin,
+        width,
+        height,
+    })
+}
+
+proptest! {
+    #[test]
+    fn intersection_is_commutative(a in rectangle(), b in rectangle()) {
+        prop_assert_eq!(a.intersection(&b), b.intersection(&a));
+        prop_assert_eq!(a.intersects(&b), b.intersects(&a));
+    }
+
+    #[test]
+    fn intersection_is_inside_both(a in rectangle(), b in rectangle()) {
+        if let Some(overlap) = a.intersection(&b) {
+            prop_assert!(!overlap.is_empty());
+            prop_assert!(a.contains(&overlap));
+            prop_assert!(b.contains(&overlap));
+            prop_assert!(overlap.area() <= a.area().min(b.area()));
+        }
+    }
+
+    #[test]
+    fn points_in_the_intersection_are_in_both(a in rectangle(), b in rectangle(), p in point()) {
+        let in_overlap = a.intersection(&b).is_some_and(|overlap| overlap.contains_point(p));
+        prop_assert_eq!(in_overlap, a.contains_point(p) && b.contains_point(p));
+    }
+
+    #[test]
+    fn intersecting_with_itself_is_identity(a in rectangle()) {
+        let expected = if a.is_empty() { None } else { Some(a) };
+        prop_assert_eq!(a.intersection(&a), expected);
+    }
+
+    #[test]
+    fn union_is_commutative_and_contains_both(a in rectangle(), b in rectangle()) {
+        let union = a.union(&b);
+
+        prop_assert_eq!(union, b.union(&a));
+        prop_assert!(union.contains(&a));
+        prop_assert!(union.contains(&b));
+        prop_assert!(union.area() >= a.area().max(b.area()));
+    }
+
+    #[test]
+    fn union_is_associative(a in rectangle(), b in rectangle(), c in rectangle()) {
+        prop_assert_eq!(a.union(&b).union(&c), a.union(&b.union(&c)));
+    }
+
+    #[test]
+    fn union_is_the_smallest_bound(a in rectangle(), b in rectangle()) {
+        let union = a.union(&b);
+
+        // Every edge of the union is an edge of one of the inputs.
+        prop_assert!(union.left() == a.left() || union.left() == b.left());
+        prop_assert!(union.top() == a.top() || union.top() == b.top());
+        prop_assert!(union.right() == a.right() || union.right() == b.right());
+        prop_assert!(union.bottom() == a.bottom() || union.bottom() == b.bottom());
+    }
+
+    #[test]
+    fn inclusion_exclusion_bounds_the_union(a in rectangle(), b in rectangle()) {
+        let overlap = a.intersection(&b).map_or(0, |overlap| overlap.area());
+        prop_assert!(a.area() + b.area() - overlap <= a.union(&b).area());
+    }
+
+    #[test]
+    fn translation_preserves_shape_and_overlap(
+        a in rectangle(),
+        b in rectangle(),
+        dx in -1000..1000,
+        dy in -1000..1000,
+    ) {
+        let (moved_a, moved_b) = (a.translate(dx, dy), b.translate(dx, dy));
+
+        prop_assert_eq!(moved_a.area(), a.area());
+        prop_assert_eq!(moved_a.perimeter(), a.perimeter());
+        prop_assert_eq!(
+            moved_a.intersection(&moved_b),
+            a.intersection(&b).map(|overlap| overlap.translate(dx, dy))
+        );
+    }
+
+    #[test]
+    fn scaling_multiplies_area(a in rectangle(), factor in 0..10u32) {
+        let scaled = a.scale(factor);
+
+        prop_assert_eq!(scaled.area(), a.area() * u64::from(factor * factor));
+        prop_assert_eq!(scaled.origin, a.origin);
+    }
+
+    #[test]
+    fn can_hold_variants_agree(a in rectangle(), b in rectangle()) {
+        prop_assert!(!a.can_hold(&b) || a.can_fit(&b));
+        prop_assert!(!a.can_hold(&b) || a.can_hold_rotated(&b));
+        prop_assert!(!a.can_fit(&b) || a.can_fit_rotated(&b));
+        prop_assert!(!a.can_hold_rotated(&b) || a.can_fit_rotated(&b));
+        prop_assert_eq!(a.can_fit_rotated(&b), a.can_fit_rotated(&b.rotated()));
+        prop_assert!(a.can_fit(&a) && !a.can_hold(&a));
+    }
+
+    #[test]
+    fn a_rectangle_that_fits_can_be_placed_inside(a in rectangle(), b in rectangle()) {
+        if a.can_fit(&b) {
+            prop_assert!(a.contains(&b.with_origin(a.origin)));
+        }
+    }
+}
+
+fn triangle() -> impl Strategy<Value = Triangle> {
+    (point(), point(), point()).prop_map(|(a, b, c)| Triangle::new(a, b, c))
+}
+
+fn circle() -> impl Strategy<Value = Circle> {
+    (point(), 0..200u32).prop_map(|(center, radius)| Circle::new(center, radius))
+}
+
+proptest! {
+    #[test]
+    fn triangles_contain_their_vertices_and_stay_in_bounds(t in triangle(), p in point()) {
+        let bounds = t.bounding_box();
+
+        for vertex in t.vertices {
+            prop_assert!(t.contains_point(vertex));
+            prop_assert!(bounds.contains_point(vertex));
+        }
+        if t.contains_point(p) {
+            prop_assert!(bounds.contains_point(p));
+        }
+    }
+
+    #[test]
+    fn circles_stay_in_bounds(c in circle(), p in point()) {
+        if c.contains_point(p) {
+            prop_assert!(c.bounding_box().contains_point(p));
+        }
+    }
+
+    #[test]
+    fn triangle_and_polygon_agree(t in triangle(), p in point()) {
+        // Collinear vertices aren't a simple polygon.
+        if let Ok(polygon) = Polygon::new(t.vertices.to_vec()) {
+            prop_assert_eq!(polygon.area(), t.area());
+            prop_assert_eq!(polygon.bounding_box(), t.bounding_box());
+            prop_assert_eq!(polygon.contains_point(p), t.contains_point(p));
+        } else {
+            prop_assert_eq!(t.area(), 0.0);
+        }
+    }
+
+    #[test]
+    fn rectangle_and_polygon_agree_away_from_the_far_edges(r in rectangle(), p in point()) {
+        prop_assume!(!r.is_empty());
+        let Point { x, y } = r.origin;
+        let (right, bottom) = (r.right() as i32, r.bottom() as i32);
+        let polygon = Polygon::new(vec![
+            r.origin,
+            Point::new(right, y),
+            Point::new(right, bottom),
+            Point::new(x, bottom),
+        ])
+        .unwrap();
+
+        prop_assert_eq!(Shape::area(&polygon), Shape::area(&r));
+        prop_assert_eq!(Shape::perimeter(&polygon), Shape::perimeter(&r));
+        // The polygon is closed and the rectangle isn't, so they only
+        // differ on the right and bottom edges.
+        if p.x != right && p.y != bottom {
+            prop_assert_eq!(polygon.contains_point(p), r.contains_point(p));
+        }
+    }
+}
+
+// Shapes small enough to check every grid point they could share.
+fn small_shape() -> impl Strategy<Value = Box<dyn Shape>> {
+    let point = || (-5..5, -5..5).prop_map(|(x, y)| Point::new(x, y));
+    prop_oneof![
+        (point(), point(), point())
+            .prop_map(|(a, b, c)| Box::new(Triangle::new(a, b, c)) as Box<dyn Shape>),
+        (point(), 0..5u32).prop_map(|(center, radius)| {
+            Box::new(Circle::new(center, radius)) as Box<dyn Shape>
+        }),
+        (point(), 0..8u32, 0..8u32).prop_map(|(origin, width, height)| {
+            Box::new(Rectangle {
+                origin,
+                width,
+                height,
+            }) as Box<dyn Shape>
+        }),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn shapes_sharing_a_grid_point_overlap(a in small_shape(), b in small_shape()) {
+        let shared = (-15..15).any(|x| {
+            (-15..15).any(|y| a.contains_point(Point::new(x, y)) && b.contains_point(Point::new(x, y)))
+        });
+
+        if shared {
+            prop_assert!(overlaps(&*a, &*b));
+        }
+        prop_assert_eq!(overlaps(&*a, &*b), overlaps(&*b, &*a));
+    }
+
+    #[test]
+    fn rectangles_overlap_when_they_intersect(a in rectangle(), b in rectangle()) {
+        prop_assert_eq!(overlaps(&a, &b), a.intersects(&b));
+    }
+}
@@ -0,0 +1,112 @@
+use proptest::prelude::*;
+use rectangles::spatial::{BruteForce, ItemId, QuadTree, SpatialIndex};
+use rectangles::{Point, Rectangle};
+
+// Rectangles crowd into a small area so the quadtree splits deeply, with a
+// few huge ones thrown in that straddle every split.
+fn rectangle() -> impl Strategy<Value = Rectangle> {
+    prop_oneof![
+        8 => (-200..200, -200..200, 0..40u32, 0..40u32),
+        1 => (any::<i32>(), any::<i32>(), any::<u32>(), any::<u32>()),
+    ]
+    .prop_map(|(x, y, width, height)| Rectangle::at(x, y, width, height))
+}
+
+fn point() -> impl Strategy<Value = Point> {
+    prop_oneof![
+        8 => (-250..250, -250..250),
+        1 => (any::<i32>(), any::<i32>()),
+    ]
+    .prop_map(|(x, y)| Point::new(x, y))
+}
+
+#[derive(Debug, Clone)]
+enum Op {
+    Insert(Rectangle),
+    // Picks one of the ids handed out so far, live or already removed.
+    Remove(prop::sample::Index),
+    Query(Rectangle),
+    At(Point),
+    Nearest(Point),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        6 => rectangle().prop_map(Op::Insert),
+        2 => any::<prop::sample::Index>().prop_map(Op::Remove),
+        2 => rectangle().prop_map(Op::Query),
+        1 => point().prop_map(Op::At),
+        1 => point().prop_map(Op::Nearest),
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(200))]
+
+    #[test]
+    fn quadtree_matches_brute_force(ops in prop::collection::vec(op(), 1..300)) {
+        let mut tree = QuadTree::new();
+        let mut reference = BruteForce::new();
+        let mut ids: Vec<ItemId> = Vec::new();
+
+        for op in ops {
+            match op {
+                Op::Insert(rect) => {
+                    let id = tree.insert(rect);
+                    prop_assert_eq!(id, reference.insert(rect));
+                    ids.push(id);
+                }
+                Op::Remove(index) if !ids.is_empty() => {
+                    let id = *index.get(&ids);
+                    prop_assert_eq!(tree.remove(id), reference.remove(id));
+                }
+                Op::Remove(_) => {}
+                Op::Query(region) => prop_assert_eq!(tree.query(&region), reference.query(&region)),
+                Op::At(point) => prop_assert_eq!(tree.at(point), reference.at(point)),
+                Op::Nearest(point) => prop_assert_eq!(tree.nearest(point), reference.nearest(point)),
+            }
+            prop_assert_eq!(tree.len(), reference.len());
+        }
+
+        prop_assert_eq!(tree.items(), reference.items());
+        prop_assert_eq!(tree.overlapping_pairs(), reference.overlapping_pairs());
+    }
+}
+
+#[test]
+fn thousands_of_rectangles() {
+    // A fixed xorshift sequence, so failures are reproducible.
+    let mut state = 0x9e37_79b9_u32;
+    let mut next = move |max: u32| {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state % max
+    };
+
+    let mut tree = QuadTree::new();
+    let mut reference = BruteForce::new();
+    for _ in 0..5_000 {
+        let rect = Rectangle::at(
+            next(10_000) as i32,
+            next(10_000) as i32,
+            1 + next(50),
+            1 + next(50),
+        );
+        tree.insert(rect);
+        reference.insert(rect);
+    }
+    for id in reference.items().into_iter().map(|(id, _)| id).step_by(3) {
+        assert_eq!(tree.remove(id), reference.remove(id));
+    }
+
+    for _ in 0..200 {
+        let point = Point::new(next(12_000) as i32 - 1_000, next(12_000) as i32 - 1_000);
+        let region = Rectangle::at(point.x, point.y, next(500), next(500));
+
+        assert_eq!(tree.query(&region), reference.query(&region));
+        assert_eq!(tree.at(point), reference.at(point));
+        assert_eq!(tree.nearest(point), reference.nearest(point));
+    }
+    assert_eq!(tree.overlapping_pairs(), reference.overlapping_pairs());
+}
@@ -1,9 +1,33 @@
-#![allow(unused_imports)]
+pub use arithmetic::Int;
+use rand::distributions::uniform::SampleUniform;
+use random_utils::Random;
+use std::ops::RangeInclusive;
 
-use rand;
+// Panics if x is already the type's MAX, in release builds too.
+pub fn add_one<T: Int>(x: T) -> T {
+    arithmetic::add(x, T::one())
+}
+
+pub fn checked_add_one<T: Int>(x: T) -> Option<T> {
+    arithmetic::checked_add(x, T::one())
+}
+
+pub fn saturating_add_one<T: Int>(x: T) -> T {
+    arithmetic::saturating_add(x, T::one())
+}
 
-pub fn add_one(x: i32) -> i32 {
-    x + 1
+pub fn wrapping_add_one<T: Int>(x: T) -> T {
+    arithmetic::wrapping_add(x, T::one())
+}
+
+// Adds a number picked from `range`, both ends included. Panics
+// if the sum overflows or the range is empty.
+pub fn add_random<T: Int + SampleUniform>(
+    x: T,
+    range: RangeInclusive<T>,
+    random: &mut Random,
+) -> T {
+    arithmetic::add(x, random.int_in(range))
 }
 
 #[cfg(test)]
@@ -14,4 +38,37 @@ mod tests {
     fn it_works() {
         assert_eq!(3, add_one(2));
     }
+
+    #[test]
+    fn every_flavour_adds_one() {
+        assert_eq!(checked_add_one(-1i8), Some(0));
+        assert_eq!(saturating_add_one(254u8), u8::MAX);
+        assert_eq!(wrapping_add_one(u8::MAX), 0);
+    }
+
+    #[test]
+    fn add_random_is_repeatable_with_a_seed() {
+        let mut first = Random::seeded(1);
+        let mut second = Random::seeded(1);
+        for _ in 0..100 {
+            let sum = add_random(10, 1..=6, &mut first);
+            assert!((11..=16).contains(&sum));
+            assert_eq!(add_random(10, 1..=6, &mut second), sum);
+        }
+
+        assert_eq!(add_random(u8::MAX - 3, 3..=3, &mut first), u8::MAX);
+        assert_eq!(add_random(-5i64, -5..=-5, &mut first), -10);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to add with overflow")]
+    fn add_random_panics_on_overflow() {
+        add_random(i32::MAX, 1..=1, &mut Random::seeded(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to add with overflow")]
+    fn add_one_panics_instead_of_wrapping() {
+        add_one(i32::MAX);
+    }
 }
@@ -1,5 +1,24 @@
-pub fn add_two(x: i32) -> i32 {
-    x + 2
+pub use arithmetic::Int;
+
+// Panics if x + 2 is past the type's MAX, in release builds too.
+pub fn add_two<T: Int>(x: T) -> T {
+    arithmetic::add(x, two())
+}
+
+pub fn checked_add_two<T: Int>(x: T) -> Option<T> {
+    arithmetic::checked_add(x, two())
+}
+
+pub fn saturating_add_two<T: Int>(x: T) -> T {
+    arithmetic::saturating_add(x, two())
+}
+
+pub fn wrapping_add_two<T: Int>(x: T) -> T {
+    arithmetic::wrapping_add(x, two())
+}
+
+fn two<T: Int>() -> T {
+    T::one() + T::one()
 }
 
 #[cfg(test)]
@@ -10,4 +29,18 @@ mod tests {
     fn it_works() {
         assert_eq!(4, add_two(2));
     }
+
+    // Two, not one: the last step short of MAX overflows too.
+    #[test]
+    fn adds_two_up_to_max() {
+        assert_eq!(checked_add_two(u8::MAX - 2), Some(u8::MAX));
+        assert_eq!(checked_add_two(u8::MAX - 1), None);
+        assert_eq!(wrapping_add_two(u8::MAX), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to add with overflow")]
+    fn add_two_panics_instead_of_wrapping() {
+        add_two(u8::MAX - 1);
+    }
 }
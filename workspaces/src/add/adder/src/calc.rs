@@ -0,0 +1,243 @@
+// An integer calculator: tokenize, parse into a tree, then
+// evaluate the tree. Every operation goes through the checked
+// arithmetic crate that add_one and add_two are built on, so
+// overflow and division by zero are errors instead of panics.
+
+mod lexer;
+mod parser;
+
+use arithmetic::Int;
+use parser::{BinaryOp, Expr, ExprKind};
+use std::fmt;
+
+// A range of byte offsets into the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    // From the start of this span to the end of `other`.
+    fn to(self, other: Span) -> Span {
+        Span {
+            start: self.start,
+            end: other.end,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub message: String,
+    // The part of the input the error is about. Empty at the end
+    // of the input when something is missing.
+    pub span: Span,
+}
+
+impl Error {
+    fn new(message: impl Into<String>, span: Span) -> Error {
+        Error {
+            message: message.into(),
+            span,
+        }
+    }
+
+    // The column (from 1, counting characters) the error starts
+    // at.
+    pub fn column(&self, input: &str) -> usize {
+        column(input, self.span.start)
+    }
+
+    // The input with the error's span underlined on the next
+    // line, e.g.
+    //
+    //     1 + (2 * 3
+    //         ^
+    pub fn underline(&self, input: &str) -> String {
+        let before = input[..self.span.start].chars().count();
+        let width = input[self.span.start..self.span.end].chars().count().max(1);
+        format!("{input}\n{}{}", " ".repeat(before), "^".repeat(width))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+// The column (from 1, counting characters) of a byte offset.
+fn column(input: &str, offset: usize) -> usize {
+    input[..offset].chars().count() + 1
+}
+
+// Works out `input` in whatever integer type T is; i64 for the
+// calculator itself.
+pub fn evaluate<T: Int>(input: &str) -> Result<T, Error> {
+    let tokens = lexer::tokenize(input)?;
+    let expr = parser::parse(&tokens, input)?;
+    eval(input, &expr)
+}
+
+// Recurses only into parentheses and minus signs, which the
+// parser limits; chains like 1 + 1 + 1 are worked out in a loop.
+fn eval<T: Int>(input: &str, expr: &Expr) -> Result<T, Error> {
+    match &expr.kind {
+        ExprKind::Number(digits) => T::from_str_radix(&input[digits.start..digits.end], 10)
+            .map_err(|_| Error::new("this number is too large", *digits)),
+        ExprKind::Negate(operand) => {
+            // A negative literal is parsed with its sign, since MIN
+            // has no positive counterpart to negate
+            if let ExprKind::Number(digits) = &operand.kind
+                && T::min_value() < T::zero()
+            {
+                let literal = format!("-{}", &input[digits.start..digits.end]);
+                return T::from_str_radix(&literal, 10)
+                    .map_err(|_| Error::new("this number is too large", expr.span));
+            }
+            let value = eval::<T>(input, operand)?;
+            arithmetic::checked_sub(T::zero(), value).ok_or_else(|| too_large(expr.span))
+        }
+        ExprKind::Chain { first, rest } => {
+            let mut value = eval::<T>(input, first)?;
+            for (op, operand) in rest {
+                let right = eval::<T>(input, operand)?;
+                // Errors point at the chain up to here, which is
+                // what the operation applies to
+                let span = first.span.to(operand.span);
+                value = match op {
+                    BinaryOp::Add => arithmetic::checked_add(value, right),
+                    BinaryOp::Subtract => arithmetic::checked_sub(value, right),
+                    BinaryOp::Multiply => arithmetic::checked_mul(value, right),
+                    BinaryOp::Divide if right.is_zero() => {
+                        return Err(Error::new("division by zero", span));
+                    }
+                    BinaryOp::Divide => arithmetic::checked_div(value, right),
+                }
+                .ok_or_else(|| too_large(span))?;
+            }
+            Ok(value)
+        }
+    }
+}
+
+fn too_large(span: Span) -> Error {
+    Error::new("the result is too large", span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(input: &str) -> (String, usize) {
+        let err = evaluate::<i64>(input).unwrap_err();
+        (err.message.clone(), err.column(input))
+    }
+
+    #[test]
+    fn arithmetic() {
+        let cases = [
+            ("1 + 2 * 3", 7),
+            ("(1 + 2) * 3", 9),
+            ("10 - 4 - 3", 3),
+            ("7 / 2", 3),
+            ("-7 / 2", -3),
+            ("-(2 + 3) * -2", 10),
+            ("--5", 5),
+            ("41 + 1", 42),
+            ("40 + 2", 42),
+            ("0", 0),
+            ("((7)) * (2)", 14),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(evaluate::<i64>(input), Ok(expected), "{input}");
+        }
+    }
+
+    #[test]
+    fn overflow_is_an_error_in_every_width() {
+        assert_eq!(evaluate::<i8>("100 + 27"), Ok(127));
+        assert_eq!(
+            evaluate::<i8>("126 + 2").unwrap_err().message,
+            "the result is too large"
+        );
+        assert_eq!(evaluate::<i8>("-128"), Ok(-128));
+        assert_eq!(
+            evaluate::<i8>("-129"),
+            Err(Error::new(
+                "this number is too large",
+                Span { start: 0, end: 4 }
+            ))
+        );
+        assert_eq!(evaluate::<i8>("-127 - 1"), Ok(-128));
+        assert_eq!(evaluate::<i64>("-9223372036854775808"), Ok(i64::MIN));
+        assert!(evaluate::<i64>("-9223372036854775807 - 1 / -1").is_ok());
+        assert_eq!(
+            error("(-9223372036854775807 - 1) / -1"),
+            (String::from("the result is too large"), 1)
+        );
+        assert_eq!(
+            error("2 * 9999999999999999999"),
+            (String::from("this number is too large"), 5)
+        );
+        assert_eq!(
+            evaluate::<u8>("-1").unwrap_err().message,
+            "the result is too large"
+        );
+    }
+
+    #[test]
+    fn division_by_zero_points_at_the_division() {
+        assert_eq!(
+            error("1 + 6 / (3 - 3)"),
+            (String::from("division by zero"), 5)
+        );
+        assert_eq!(
+            error("8 / 2 / 0 + 1"),
+            (String::from("division by zero"), 1)
+        );
+    }
+
+    #[test]
+    fn long_chains_dont_overflow_the_stack() {
+        let input = vec!["1"; 100_000].join("+");
+        assert_eq!(evaluate::<i64>(&input), Ok(100_000));
+
+        let input = format!("{}2", "1 * ".repeat(100_000));
+        assert_eq!(evaluate::<i64>(&input), Ok(2));
+    }
+
+    #[test]
+    fn deep_nesting_is_an_error() {
+        let input = format!("{}1{}", "(".repeat(20_000), ")".repeat(20_000));
+        let (message, column) = error(&input);
+        assert_eq!(message, "nested more than 256 deep");
+        assert_eq!(column, 257);
+
+        let (message, column) = error(&format!("{}1", "-".repeat(100_000)));
+        assert_eq!(message, "nested more than 256 deep");
+        assert_eq!(column, 257);
+
+        let input = format!("{}1{}", "(".repeat(256), ")".repeat(256));
+        assert_eq!(evaluate::<i64>(&input), Ok(1));
+    }
+
+    #[test]
+    fn underlines_the_span() {
+        let input = "1 + é + (2";
+        let err = evaluate::<i64>(input).unwrap_err();
+        assert_eq!(err.column(input), 5);
+        assert_eq!(err.underline(input), "1 + é + (2\n    ^");
+
+        let input = "1 / (2 - 2)";
+        let err = evaluate::<i64>(input).unwrap_err();
+        assert_eq!(err.underline(input), "1 / (2 - 2)\n^^^^^^^^^^^");
+
+        let input = "1 +";
+        let err = evaluate::<i64>(input).unwrap_err();
+        assert_eq!(err.underline(input), "1 +\n   ^");
+    }
+}
@@ -0,0 +1,123 @@
+use super::{Error, Span};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    // The digits are left as text until evaluation, which knows
+    // the number type they need to fit in
+    Number,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LeftParen,
+    RightParen,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+impl TokenKind {
+    // How the token is named in error messages.
+    pub fn describe(self) -> &'static str {
+        match self {
+            TokenKind::Number => "a number",
+            TokenKind::Plus => "'+'",
+            TokenKind::Minus => "'-'",
+            TokenKind::Star => "'*'",
+            TokenKind::Slash => "'/'",
+            TokenKind::LeftParen => "'('",
+            TokenKind::RightParen => "')'",
+        }
+    }
+}
+
+pub fn tokenize(input: &str) -> Result<Vec<Token>, Error> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let kind = match c {
+            _ if c.is_whitespace() => continue,
+            '+' => TokenKind::Plus,
+            '-' => TokenKind::Minus,
+            '*' => TokenKind::Star,
+            '/' => TokenKind::Slash,
+            '(' => TokenKind::LeftParen,
+            ')' => TokenKind::RightParen,
+            '0'..='9' => {
+                let mut end = start + 1;
+                while let Some(&(i, '0'..='9')) = chars.peek() {
+                    end = i + 1;
+                    chars.next();
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Number,
+                    span: Span { start, end },
+                });
+                continue;
+            }
+            _ => {
+                return Err(Error::new(
+                    format!("unexpected character '{c}'"),
+                    Span {
+                        start,
+                        end: start + c.len_utf8(),
+                    },
+                ));
+            }
+        };
+        tokens.push(Token {
+            kind,
+            span: Span {
+                start,
+                end: start + 1,
+            },
+        });
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(input: &str) -> Vec<TokenKind> {
+        tokenize(input)
+            .unwrap()
+            .into_iter()
+            .map(|token| token.kind)
+            .collect()
+    }
+
+    #[test]
+    fn splits_operators_and_numbers() {
+        use TokenKind::*;
+
+        assert_eq!(
+            kinds("12+(3 * -4)/5"),
+            [
+                Number, Plus, LeftParen, Number, Star, Minus, Number, RightParen, Slash, Number
+            ]
+        );
+        assert_eq!(kinds(" \t "), []);
+    }
+
+    #[test]
+    fn numbers_keep_their_position() {
+        let tokens = tokenize("  123 4").unwrap();
+
+        assert_eq!(tokens[0].span, Span { start: 2, end: 5 });
+        assert_eq!(tokens[1].span, Span { start: 6, end: 7 });
+    }
+
+    #[test]
+    fn rejects_unknown_characters() {
+        let err = tokenize("1 + é").unwrap_err();
+
+        assert_eq!(err.message, "unexpected character 'é'");
+        assert_eq!(err.span, Span { start: 4, end: 6 });
+    }
+}
@@ -0,0 +1,309 @@
+// Recursive descent over this grammar, lowest precedence first:
+//
+//     expr    = term (("+" | "-") term)*
+//     term    = unary (("*" | "/") unary)*
+//     unary   = "-" unary | primary
+//     primary = NUMBER | "(" expr ")"
+
+use super::lexer::{Token, TokenKind};
+use super::{Error, Span, column};
+
+// How deep parentheses and minus signs can nest. Parsing and
+// evaluating recurse once per level, so without a limit one
+// long enough line would overflow the stack.
+const MAX_DEPTH: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprKind {
+    // Where the digits are; the expression's own span also
+    // covers any parentheses around them
+    Number(Span),
+    Negate(Box<Expr>),
+    // Operations of the same precedence, done left to right: 1 -
+    // 2 - 3 is 1 followed by (-, 2) and (-, 3). Kept flat rather
+    // than as a tree so a long chain doesn't make anything
+    // recurse deeply, dropping it included.
+    Chain {
+        first: Box<Expr>,
+        rest: Vec<(BinaryOp, Expr)>,
+    },
+}
+
+// Every expression knows the part of the input it came from, so
+// evaluation errors can point at it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+pub fn parse(tokens: &[Token], input: &str) -> Result<Expr, Error> {
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        input,
+        depth: 0,
+    };
+    let expr = parser.expr()?;
+
+    match parser.peek() {
+        None => Ok(expr),
+        Some(token) if token.kind == TokenKind::RightParen => {
+            Err(Error::new("unmatched ')'", token.span))
+        }
+        Some(token) => Err(Error::new(
+            format!("expected an operator, found {}", token.kind.describe()),
+            token.span,
+        )),
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    // For columns in messages, and where "end of input" errors
+    // point
+    input: &'a str,
+    // How many parentheses and minus signs we're inside
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek()?;
+        self.position += 1;
+        Some(token)
+    }
+
+    fn end_of_input(&self) -> Span {
+        Span {
+            start: self.input.len(),
+            end: self.input.len(),
+        }
+    }
+
+    // Parses `nested` one level further in, for the token that
+    // opened the level.
+    fn nest(
+        &mut self,
+        opener: Token,
+        nested: fn(&mut Self) -> Result<Expr, Error>,
+    ) -> Result<Expr, Error> {
+        if self.depth == MAX_DEPTH {
+            return Err(Error::new(
+                format!("nested more than {MAX_DEPTH} deep"),
+                opener.span,
+            ));
+        }
+        self.depth += 1;
+        let expr = nested(self);
+        self.depth -= 1;
+        expr
+    }
+
+    // Parses operands separated by any of `ops` into a Chain,
+    // to be worked out from the left, so 1 - 2 - 3 is
+    // (1 - 2) - 3.
+    fn binary(
+        &mut self,
+        ops: &[(TokenKind, BinaryOp)],
+        operand: fn(&mut Self) -> Result<Expr, Error>,
+    ) -> Result<Expr, Error> {
+        let first = operand(self)?;
+        let mut rest = Vec::new();
+        while let Some(&(_, op)) = self
+            .peek()
+            .and_then(|token| ops.iter().find(|(kind, _)| *kind == token.kind))
+        {
+            self.next();
+            rest.push((op, operand(self)?));
+        }
+
+        let Some((_, last)) = rest.last() else {
+            return Ok(first);
+        };
+        Ok(Expr {
+            span: first.span.to(last.span),
+            kind: ExprKind::Chain {
+                first: Box::new(first),
+                rest,
+            },
+        })
+    }
+
+    fn expr(&mut self) -> Result<Expr, Error> {
+        self.binary(
+            &[
+                (TokenKind::Plus, BinaryOp::Add),
+                (TokenKind::Minus, BinaryOp::Subtract),
+            ],
+            Parser::term,
+        )
+    }
+
+    fn term(&mut self) -> Result<Expr, Error> {
+        self.binary(
+            &[
+                (TokenKind::Star, BinaryOp::Multiply),
+                (TokenKind::Slash, BinaryOp::Divide),
+            ],
+            Parser::unary,
+        )
+    }
+
+    fn unary(&mut self) -> Result<Expr, Error> {
+        match self.peek() {
+            Some(minus) if minus.kind == TokenKind::Minus => {
+                self.next();
+                let operand = self.nest(minus, Parser::unary)?;
+                Ok(Expr {
+                    span: minus.span.to(operand.span),
+                    kind: ExprKind::Negate(Box::new(operand)),
+                })
+            }
+            _ => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, Error> {
+        let Some(token) = self.next() else {
+            return Err(Error::new(
+                "expected a number or '(', found the end of the input",
+                self.end_of_input(),
+            ));
+        };
+
+        match token.kind {
+            TokenKind::Number => Ok(Expr {
+                kind: ExprKind::Number(token.span),
+                span: token.span,
+            }),
+            TokenKind::LeftParen => {
+                let inner = self.nest(token, Parser::expr)?;
+                match self.next() {
+                    Some(close) if close.kind == TokenKind::RightParen => Ok(Expr {
+                        kind: inner.kind,
+                        span: token.span.to(close.span),
+                    }),
+                    Some(other) => Err(Error::new(
+                        format!(
+                            "expected ')' to close the '(' at column {}, found {}",
+                            column(self.input, token.span.start),
+                            other.kind.describe()
+                        ),
+                        other.span,
+                    )),
+                    None => Err(Error::new("this '(' is never closed", token.span)),
+                }
+            }
+            other => Err(Error::new(
+                format!("expected a number or '(', found {}", other.describe()),
+                token.span,
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::lexer::tokenize;
+    use super::*;
+
+    fn parse_str(input: &str) -> Result<Expr, Error> {
+        parse(&tokenize(input)?, input)
+    }
+
+    // The tree written back out with every operation in
+    // parentheses, to check grouping.
+    fn show(input: &str, expr: &Expr) -> String {
+        match &expr.kind {
+            ExprKind::Number(digits) => input[digits.start..digits.end].to_string(),
+            ExprKind::Negate(operand) => format!("(-{})", show(input, operand)),
+            ExprKind::Chain { first, rest } => {
+                rest.iter().fold(show(input, first), |left, (op, right)| {
+                    let op = match op {
+                        BinaryOp::Add => "+",
+                        BinaryOp::Subtract => "-",
+                        BinaryOp::Multiply => "*",
+                        BinaryOp::Divide => "/",
+                    };
+                    format!("({left} {op} {})", show(input, right))
+                })
+            }
+        }
+    }
+
+    fn grouping(input: &str) -> String {
+        show(input, &parse_str(input).unwrap())
+    }
+
+    #[test]
+    fn precedence_and_associativity() {
+        assert_eq!(grouping("1 + 2 * 3"), "(1 + (2 * 3))");
+        assert_eq!(grouping("1 - 2 - 3"), "((1 - 2) - 3)");
+        assert_eq!(grouping("8 / 4 / 2"), "((8 / 4) / 2)");
+        assert_eq!(grouping("(1 + 2) * 3"), "((1 + 2) * 3)");
+        assert_eq!(grouping("--1 * -2"), "((-(-1)) * (-2))");
+        assert_eq!(grouping("2 - -3"), "(2 - (-3))");
+        assert_eq!(grouping("((7))"), "7");
+    }
+
+    #[test]
+    fn spans_cover_the_whole_expression() {
+        let expr = parse_str(" (1 + 2) * -3 ").unwrap();
+
+        assert_eq!(expr.span, Span { start: 1, end: 13 });
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        let cases = [
+            (
+                "",
+                "expected a number or '(', found the end of the input",
+                0,
+            ),
+            (
+                "1 +",
+                "expected a number or '(', found the end of the input",
+                3,
+            ),
+            ("1 + * 2", "expected a number or '(', found '*'", 4),
+            ("(1 + 2", "this '(' is never closed", 0),
+            (
+                "(1 + 2 3",
+                "expected ')' to close the '(' at column 1, found a number",
+                7,
+            ),
+            ("1 + 2)", "unmatched ')'", 5),
+            ("1 2", "expected an operator, found a number", 2),
+            ("()", "expected a number or '(', found ')'", 1),
+            // Columns count characters, not bytes
+            (
+                "\u{3000}(1 2",
+                "expected ')' to close the '(' at column 2, found a number",
+                6,
+            ),
+        ];
+        for (input, message, column) in cases {
+            let err = parse_str(input).unwrap_err();
+            assert_eq!(
+                (err.message.as_str(), err.span.start),
+                (message, column),
+                "{input:?}"
+            );
+        }
+    }
+}
@@ -1,4 +1,112 @@
+mod calc;
+
+use random_utils::Random;
+use std::env;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::process;
+
+const USAGE: &str = "\
+usage: adder                 read expressions from stdin, one per line
+       adder -e EXPRESSION   work out a single expression
+       adder --demo [SEED]   show off the random helpers
+
+Expressions use whole numbers, + - * / and parentheses, e.g.
+    adder -e '-(1 + 2) * 3'";
+
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        [] => repl(),
+        ["-e", expression] => {
+            if !calculate(expression) {
+                process::exit(1);
+            }
+        }
+        ["--demo"] => demo(Random::from_entropy()),
+        ["--demo", seed] => match seed.parse() {
+            Ok(seed) => demo(Random::seeded(seed)),
+            Err(_) => usage_error("the seed has to be a whole number"),
+        },
+        ["-h" | "--help"] => println!("{USAGE}"),
+        _ => usage_error("unexpected arguments"),
+    }
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("[ERROR] {message}");
+    eprintln!("{USAGE}");
+    process::exit(2);
+}
+
+// Prints the value of the expression, or what's wrong with it.
+// Returns whether it worked.
+fn calculate(expression: &str) -> bool {
+    match calc::evaluate::<i64>(expression) {
+        Ok(value) => {
+            println!("{value}");
+            true
+        }
+        Err(err) => {
+            eprintln!(
+                "[ERROR] column {}: {err}\n{}",
+                err.column(expression),
+                err.underline(expression)
+            );
+            false
+        }
+    }
+}
+
+// One expression per line until the input ends or `quit`. Bad
+// expressions are reported and the loop carries on.
+fn repl() {
+    let stdin = io::stdin();
+    // Only prompt when someone is typing
+    let interactive = stdin.is_terminal();
+
+    let mut lines = stdin.lock().lines();
+    loop {
+        if interactive {
+            print!("> ");
+            let _ = io::stdout().flush();
+        }
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(err)) => {
+                eprintln!("[ERROR] {err}");
+                process::exit(1);
+            }
+            None => break,
+        };
+
+        match line.trim() {
+            "" => continue,
+            "quit" | "exit" => break,
+            expression => {
+                calculate(expression);
+            }
+        }
+    }
+}
+
+fn demo(mut random: Random) {
     let num = 10;
     println!("Hello, world! {num} plus one is {}!", add_one::add_one(num));
+
+    let sum = add_one::add_random(num, 1..=6, &mut random);
+    println!("Rolling a die: {num} plus {} is {sum}!", sum - num);
+
+    let mut players = ["Ferris", "Corro", "Carol", "Dave"];
+    random.shuffle(&mut players);
+    println!("Turn order: {}", players.join(", "));
+
+    let prizes = [("a sticker", 6), ("a mug", 3), ("a T-shirt", 1)];
+    let prize = random
+        .weighted_choice(&prizes)
+        .expect("prizes have weights");
+    println!("{} wins {prize}!", players[0]);
+
+    let lucky = random.sample(1..=100, 3);
+    println!("Lucky numbers: {lucky:?}");
 }
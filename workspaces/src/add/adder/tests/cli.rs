@@ -0,0 +1,93 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn adder(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_adder"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to start adder");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn text(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn evaluates_one_expression() {
+    let output = adder(&["-e", "-(1 + 2) * 3 - 10 / 4"], "");
+
+    assert!(output.status.success());
+    assert_eq!(text(output.stdout), "-11\n");
+}
+
+#[test]
+fn parse_errors_show_where() {
+    let output = adder(&["-e", "2 * (3 + )"], "");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        text(output.stderr),
+        "[ERROR] column 10: expected a number or '(', found ')'\n\
+         2 * (3 + )\n         ^\n"
+    );
+}
+
+#[test]
+fn repl_keeps_going_after_errors() {
+    let output = adder(&[], "1 + 1\n\n7 / 0\n  40 + 2  \nquit\n99\n");
+
+    assert!(output.status.success());
+    assert_eq!(text(output.stdout), "2\n42\n");
+    assert_eq!(
+        text(output.stderr),
+        "[ERROR] column 1: division by zero\n7 / 0\n^^^^^\n"
+    );
+}
+
+#[test]
+fn repl_survives_huge_expressions() {
+    let nested = format!("{}1{}", "(".repeat(20_000), ")".repeat(20_000));
+    let chain = vec!["1"; 100_000].join("+");
+    let output = adder(&[], &format!("{nested}\n{chain}\n1 + 1\n"));
+
+    assert!(output.status.success());
+    assert_eq!(text(output.stdout), "100000\n2\n");
+    let stderr = text(output.stderr);
+    assert!(stderr.starts_with("[ERROR] column 257: nested more than 256 deep\n"));
+}
+
+#[test]
+fn demo_is_repeatable_with_a_seed() {
+    let first = adder(&["--demo", "7"], "");
+    let second = adder(&["--demo", "7"], "");
+
+    assert!(first.status.success());
+    assert_eq!(first.stdout, second.stdout);
+
+    let stdout = text(first.stdout);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines[0], "Hello, world! 10 plus one is 11!");
+    assert!(lines[1].starts_with("Rolling a die: 10 plus "));
+    assert!(lines[2].starts_with("Turn order: "));
+    assert!(lines[4].starts_with("Lucky numbers: ["));
+}
+
+#[test]
+fn bad_arguments_are_usage_errors() {
+    for args in [&["--demo", "seven"][..], &["-e"], &["1 + 1"]] {
+        let output = adder(args, "");
+
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        assert!(text(output.stderr).contains("usage: adder"));
+    }
+}
@@ -0,0 +1,139 @@
+use num_bigint::BigUint;
+use num_traits::{PrimInt, WrappingAdd};
+
+// Every primitive integer type, signed or not, from i8 to u128.
+// This is the one integer trait the workspace's crates share,
+// and the functions below work with any of them.
+pub trait Int: PrimInt + WrappingAdd {}
+
+impl<T: PrimInt + WrappingAdd> Int for T {}
+
+// Panics if the sum doesn't fit, in release builds too, instead
+// of quietly wrapping around. It never widens the result: code
+// that must not overflow at all, like billing totals, has to
+// use add_big instead.
+pub fn add<T: Int>(left: T, right: T) -> T {
+    checked_add(left, right).expect("attempt to add with overflow (add_big never overflows)")
+}
+
+// None if the sum doesn't fit.
+pub fn checked_add<T: Int>(left: T, right: T) -> Option<T> {
+    left.checked_add(&right)
+}
+
+// Stops at the type's MAX or MIN instead of overflowing.
+pub fn saturating_add<T: Int>(left: T, right: T) -> T {
+    left.saturating_add(right)
+}
+
+// Wraps around past MAX (or MIN), like release builds used to.
+pub fn wrapping_add<T: Int>(left: T, right: T) -> T {
+    left.wrapping_add(&right)
+}
+
+// None if the difference doesn't fit.
+pub fn checked_sub<T: Int>(left: T, right: T) -> Option<T> {
+    left.checked_sub(&right)
+}
+
+// None if the product doesn't fit.
+pub fn checked_mul<T: Int>(left: T, right: T) -> Option<T> {
+    left.checked_mul(&right)
+}
+
+// Rounds towards zero. None when dividing by zero, or for the one
+// quotient that doesn't fit, MIN / -1.
+pub fn checked_div<T: Int>(left: T, right: T) -> Option<T> {
+    left.checked_div(&right)
+}
+
+// The arbitrary-precision fallback, for sums that must never
+// overflow. Takes any unsigned integer or another BigUint.
+pub fn add_big(left: impl Into<BigUint>, right: impl Into<BigUint>) -> BigUint {
+    left.into() + right.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Boundary checks for one integer type, generated for every
+    // width below.
+    macro_rules! boundaries {
+        ($($name:ident: $t:ty,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (min, max) = (<$t>::MIN, <$t>::MAX);
+                    let (one, two): ($t, $t) = (1, 2);
+
+                    assert_eq!(add(max - one, one), max);
+                    assert_eq!(add(max - two, two), max);
+                    assert_eq!(checked_add(max, one), None);
+                    assert_eq!(checked_add(max - one, two), None);
+                    assert_eq!(checked_add(max, 0), Some(max));
+                    assert_eq!(saturating_add(max, max), max);
+                    assert_eq!(saturating_add(max - one, two), max);
+                    assert_eq!(wrapping_add(max, one), min);
+                    assert_eq!(wrapping_add(max, two), min + one);
+                    assert_eq!(add(min, one), min + one);
+
+                    assert_eq!(checked_sub(min, one), None);
+                    assert_eq!(checked_sub(max, max), Some(0));
+                    assert_eq!(checked_mul(max, two), None);
+                    assert_eq!(checked_mul(max, one), Some(max));
+                    assert_eq!(checked_div(max, 0), None);
+                    assert_eq!(checked_div(max, max), Some(one));
+
+                    // Going under MIN only happens for signed types
+                    if min != 0 {
+                        let minus_one = min.wrapping_add(max);
+                        assert_eq!(checked_add(min, minus_one), None);
+                        assert_eq!(checked_add(min, min), None);
+                        assert_eq!(saturating_add(min, minus_one), min);
+                        assert_eq!(wrapping_add(min, minus_one), max);
+                        assert_eq!(wrapping_add(min, min), 0);
+                        assert_eq!(checked_sub(max, minus_one), None);
+                        assert_eq!(checked_mul(min, minus_one), None);
+                        assert_eq!(checked_div(min, minus_one), None);
+                        assert_eq!(checked_div(min + one, minus_one), Some(max));
+                    }
+                }
+            )*
+        };
+    }
+
+    boundaries! {
+        boundaries_i8: i8,
+        boundaries_i16: i16,
+        boundaries_i32: i32,
+        boundaries_i64: i64,
+        boundaries_i128: i128,
+        boundaries_isize: isize,
+        boundaries_u8: u8,
+        boundaries_u16: u16,
+        boundaries_u32: u32,
+        boundaries_u64: u64,
+        boundaries_u128: u128,
+        boundaries_usize: usize,
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to add with overflow")]
+    fn add_panics_instead_of_wrapping() {
+        add(u64::MAX, 1);
+    }
+
+    #[test]
+    fn big_sums_never_overflow() {
+        let sum = add_big(u64::MAX, u64::MAX);
+        assert_eq!(sum.to_string(), "36893488147419103230");
+        assert_eq!(
+            add_big(u128::MAX, 1u8).to_string(),
+            "340282366920938463463374607431768211456"
+        );
+
+        let total = (0..1000).fold(BigUint::from(0u8), |total, _| add_big(total, u128::MAX));
+        assert_eq!(total, BigUint::from(u128::MAX) * 1000u32);
+    }
+}
@@ -0,0 +1,199 @@
+use rand::distributions::uniform::SampleUniform;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::ops::RangeInclusive;
+
+// A random number generator with the helpers the rest of the
+// workspace needs. Give it a seed and it produces the same
+// numbers every time, which is what tests want.
+#[derive(Debug, Clone)]
+pub struct Random {
+    rng: StdRng,
+}
+
+impl Random {
+    pub fn seeded(seed: u64) -> Random {
+        Random {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    // Seeded from the operating system, so different each run.
+    pub fn from_entropy() -> Random {
+        Random {
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    // Any number in the range, both ends included. Panics if the
+    // range is empty.
+    pub fn int_in<T: SampleUniform + PartialOrd>(&mut self, range: RangeInclusive<T>) -> T {
+        assert!(!range.is_empty(), "can't pick from an empty range");
+        self.rng.gen_range(range)
+    }
+
+    // Puts the items in a random order, each order equally likely.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        items.shuffle(&mut self.rng);
+    }
+
+    // Picks one item, with chances proportional to its weight.
+    // None if there are no items or every weight is zero.
+    pub fn weighted_choice<'a, T>(&mut self, items: &'a [(T, u32)]) -> Option<&'a T> {
+        let total: u64 = items.iter().map(|&(_, weight)| u64::from(weight)).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut pick = self.rng.gen_range(0..total);
+        for (item, weight) in items {
+            let weight = u64::from(*weight);
+            if pick < weight {
+                return Some(item);
+            }
+            pick -= weight;
+        }
+        unreachable!("pick is less than the total weight")
+    }
+
+    // Picks `count` items from `items` without replacement, each
+    // equally likely, going through them only once (reservoir
+    // sampling), so `items` can be too long to collect. Returns
+    // everything if there are fewer than `count` items.
+    pub fn sample<I: IntoIterator>(&mut self, items: I, count: usize) -> Vec<I::Item> {
+        let items = items.into_iter();
+        // `count` can be far more than there are items
+        let mut reservoir = Vec::with_capacity(count.min(items.size_hint().0));
+        for (seen, item) in items.enumerate() {
+            if reservoir.len() < count {
+                reservoir.push(item);
+                continue;
+            }
+            // Keep the new item with probability count / (seen + 1)
+            let slot = self.rng.gen_range(0..=seen);
+            if slot < count {
+                reservoir[slot] = item;
+            }
+        }
+        reservoir
+    }
+
+    // The underlying generator, for anything the helpers above
+    // don't cover.
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let mut first = Random::seeded(42);
+        let mut second = Random::seeded(42);
+
+        for _ in 0..100 {
+            assert_eq!(first.int_in(0..=1_000_000), second.int_in(0..=1_000_000));
+        }
+        assert_ne!(
+            Random::seeded(1).int_in(0..=u64::MAX),
+            Random::seeded(2).int_in(0..=u64::MAX)
+        );
+    }
+
+    #[test]
+    fn ranges_include_both_ends() {
+        let mut random = Random::seeded(7);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let n = random.int_in(-2..=3);
+            seen[(n + 2) as usize] = true;
+        }
+
+        assert!(seen.iter().all(|&seen| seen));
+        assert_eq!(random.int_in(5..=5), 5);
+        assert_eq!(random.int_in(i8::MIN..=i8::MIN), i8::MIN);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't pick from an empty range")]
+    fn empty_ranges_panic() {
+        #[allow(clippy::reversed_empty_ranges)]
+        Random::seeded(0).int_in(3..=2);
+    }
+
+    #[test]
+    fn shuffles_are_repeatable_permutations() {
+        let mut items: Vec<u32> = (0..50).collect();
+        let mut again = items.clone();
+        Random::seeded(3).shuffle(&mut items);
+        Random::seeded(3).shuffle(&mut again);
+
+        assert_eq!(items, again);
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn weighted_choice_follows_the_weights() {
+        let mut random = Random::seeded(11);
+        let items = [("never", 0), ("rare", 1), ("common", 9)];
+        let mut counts = [0; 3];
+        for _ in 0..10_000 {
+            match *random.weighted_choice(&items).unwrap() {
+                "never" => counts[0] += 1,
+                "rare" => counts[1] += 1,
+                _ => counts[2] += 1,
+            }
+        }
+
+        assert_eq!(counts[0], 0);
+        assert!((800..1200).contains(&counts[1]), "{counts:?}");
+        assert_eq!(random.weighted_choice::<u8>(&[]), None);
+        assert_eq!(random.weighted_choice(&[(1, 0), (2, 0)]), None);
+        assert!(
+            random
+                .weighted_choice(&[(1, u32::MAX), (2, u32::MAX)])
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn reservoir_samples_without_replacement() {
+        let mut random = Random::seeded(5);
+        let mut sample = random.sample(0..100_000, 10);
+
+        assert_eq!(sample.len(), 10);
+        sample.sort_unstable();
+        sample.dedup();
+        assert_eq!(sample.len(), 10);
+        assert_eq!(random.sample(0..3, 10), [0, 1, 2]);
+        assert!(random.sample(0..3, 0).is_empty());
+        assert_eq!(random.sample(0..3, usize::MAX), [0, 1, 2]);
+    }
+
+    #[test]
+    fn reservoir_sampling_is_fair() {
+        let mut random = Random::seeded(9);
+        let mut counts = [0; 10];
+        for _ in 0..10_000 {
+            for n in random.sample(0..10, 3) {
+                counts[n] += 1;
+            }
+        }
+
+        // Each number should be picked about 3000 times
+        assert!(
+            counts.iter().all(|&c| (2700..3300).contains(&c)),
+            "{counts:?}"
+        );
+        assert_eq!(
+            Random::seeded(9).sample(0..10, 3),
+            Random::seeded(9).sample(0..10, 3)
+        );
+    }
+}
@@ -1,6 +1,6 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+// The arithmetic lives in the add workspace's arithmetic crate,
+// which every crate here shares.
+pub use arithmetic::{Int, add, add_big, checked_add, saturating_add, wrapping_add};
 
 #[cfg(test)]
 mod tests {